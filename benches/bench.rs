@@ -6,6 +6,7 @@ use self::setup::{
     BenchBucket,
     BenchBuffer,
     BenchString,
+    StringInternerWith,
     BENCH_LEN_STRINGS,
     BENCH_STRING_LEN,
 };
@@ -24,7 +25,8 @@ use string_interner::backend::Backend;
 criterion_group!(
     bench_resolve,
     bench_resolve_already_filled,
-    bench_resolve_unchecked_already_filled
+    bench_resolve_unchecked_already_filled,
+    bench_resolve_backend_already_filled
 );
 criterion_group!(bench_get, bench_get_already_filled);
 criterion_group!(bench_iter, bench_iter_already_filled);
@@ -34,8 +36,113 @@ criterion_group!(
     bench_get_or_intern_fill_with_capacity,
     bench_get_or_intern_already_filled,
     bench_get_or_intern_static,
+    bench_get_or_intern_short,
+    bench_get_or_intern_large,
 );
-criterion_main!(bench_get_or_intern, bench_resolve, bench_get, bench_iter);
+criterion_group!(bench_load, bench_load_reserve_vs_naive);
+criterion_group!(bench_dedup_batch, bench_intern_dedup_batch);
+criterion_main!(
+    bench_get_or_intern,
+    bench_resolve,
+    bench_get,
+    bench_iter,
+    bench_load,
+    bench_dedup_batch
+);
+
+fn bench_load_reserve_vs_naive(c: &mut Criterion) {
+    let mut g = c.benchmark_group("load");
+    g.throughput(Throughput::Elements(BENCH_LEN_STRINGS as u64));
+    fn bench_for_backend<'i, BB: BackendBenchmark<'i>>(g: &mut BenchmarkGroup<WallTime>) {
+        let words = generate_test_strings(BENCH_LEN_STRINGS, BENCH_STRING_LEN);
+        g.bench_with_input(
+            format!("{}/{}", BB::NAME, "naive"),
+            &words,
+            |bencher, words| {
+                bencher.iter_batched_ref(
+                    || BB::setup(),
+                    |interner| {
+                        for word in words {
+                            black_box(interner.get_or_intern(word));
+                        }
+                    },
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+        g.bench_with_input(
+            format!("{}/{}", BB::NAME, "reserve"),
+            &words,
+            |bencher, words| {
+                bencher.iter_batched_ref(
+                    || BB::setup(),
+                    |interner| {
+                        interner.reserve(words.len());
+                        for word in words {
+                            black_box(interner.get_or_intern(word));
+                        }
+                    },
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+        let json = serde_json::to_string(&words).unwrap();
+        g.bench_with_input(
+            format!("{}/{}", BB::NAME, "deserialize"),
+            &json,
+            |bencher, json| {
+                bencher.iter(|| {
+                    black_box(serde_json::from_str::<StringInternerWith<'i, BB::Backend>>(json).unwrap());
+                })
+            },
+        );
+    }
+    bench_for_backend::<BenchBucket>(&mut g);
+    bench_for_backend::<BenchString>(&mut g);
+    bench_for_backend::<BenchBuffer>(&mut g);
+}
+
+fn bench_intern_dedup_batch(c: &mut Criterion) {
+    let mut g = c.benchmark_group("intern_dedup_batch");
+    g.throughput(Throughput::Elements(BENCH_LEN_STRINGS as u64));
+    fn bench_for_backend<'i, BB: BackendBenchmark<'i>>(g: &mut BenchmarkGroup<WallTime>) {
+        // Runs of 100 equal strings, as in a column of sorted categorical data.
+        let words = generate_test_strings(BENCH_LEN_STRINGS / 100, BENCH_STRING_LEN);
+        let runs = words
+            .iter()
+            .flat_map(|word| core::iter::repeat_n(word.as_str(), 100))
+            .collect::<Vec<_>>();
+        g.bench_with_input(
+            format!("{}/{}", BB::NAME, "naive"),
+            &runs,
+            |bencher, runs| {
+                bencher.iter_batched_ref(
+                    || BB::setup(),
+                    |interner| {
+                        for &word in runs {
+                            black_box(interner.get_or_intern(word));
+                        }
+                    },
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+        g.bench_with_input(
+            format!("{}/{}", BB::NAME, "dedup-batch"),
+            &runs,
+            |bencher, runs| {
+                bencher.iter_batched_ref(
+                    || BB::setup(),
+                    |interner| black_box(interner.intern_dedup_batch(runs.iter().copied())),
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+    bench_for_backend::<BenchBucket>(&mut g);
+    bench_for_backend::<BenchString>(&mut g);
+    bench_for_backend::<BenchBuffer>(&mut g);
+}
 
 fn bench_get_or_intern_static(c: &mut Criterion) {
     let mut g = c.benchmark_group("get_or_intern_static");
@@ -162,6 +269,68 @@ fn bench_get_or_intern_already_filled(c: &mut Criterion) {
     bench_for_backend::<BenchBuffer>(&mut g);
 }
 
+/// Interns tokens of one or two ASCII characters into a filled interner.
+///
+/// Run with and without the `small-string-cache` feature to compare the
+/// direct-indexed fast path against the hash map lookup.
+fn bench_get_or_intern_short(c: &mut Criterion) {
+    let mut g = c.benchmark_group("get_or_intern/short");
+    g.throughput(Throughput::Elements(BENCH_LEN_STRINGS as u64));
+    fn bench_for_backend<'i, BB: BackendBenchmark<'i>>(g: &mut BenchmarkGroup<WallTime>) {
+        g.bench_with_input(BB::NAME, &BENCH_LEN_STRINGS, |bencher, &len_words| {
+            let tokens = [
+                "(", ")", "{", "}", ";", ",", ".", "=", "==", "!=", "<=", "->", "+=", "if",
+            ];
+            let words = tokens.iter().cycle().take(len_words).collect::<Vec<_>>();
+            bencher.iter_batched_ref(
+                || BB::setup_filled(tokens),
+                |interner| {
+                    for word in &words {
+                        black_box(interner.get_or_intern(word));
+                    }
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    bench_for_backend::<BenchBucket>(&mut g);
+    bench_for_backend::<BenchString>(&mut g);
+    bench_for_backend::<BenchBuffer>(&mut g);
+}
+
+/// Interns distinct 64 KiB strings into an empty interner.
+///
+/// Measures how fast the backends copy large strings into their storage.
+fn bench_get_or_intern_large(c: &mut Criterion) {
+    const LEN_STRINGS: usize = 16;
+    const STRING_LEN: usize = 64 * 1024;
+    let mut g = c.benchmark_group("get_or_intern/large");
+    g.throughput(Throughput::Bytes((LEN_STRINGS * STRING_LEN) as u64));
+    fn bench_for_backend<'i, BB: BackendBenchmark<'i>>(g: &mut BenchmarkGroup<WallTime>) {
+        g.bench_with_input(
+            BB::NAME,
+            &(LEN_STRINGS, STRING_LEN),
+            |bencher, &(len_words, word_len)| {
+                let words = (0..len_words)
+                    .map(|i| format!("{:08}{}", i, "x".repeat(word_len - 8)))
+                    .collect::<Vec<_>>();
+                bencher.iter_batched_ref(
+                    BB::setup,
+                    |interner| {
+                        for word in &words {
+                            black_box(interner.get_or_intern(word));
+                        }
+                    },
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+    bench_for_backend::<BenchBucket>(&mut g);
+    bench_for_backend::<BenchString>(&mut g);
+    bench_for_backend::<BenchBuffer>(&mut g);
+}
+
 fn bench_resolve_already_filled(c: &mut Criterion) {
     let mut g = c.benchmark_group("resolve/already-filled");
     g.throughput(Throughput::Elements(BENCH_LEN_STRINGS as u64));
@@ -217,6 +386,39 @@ fn bench_resolve_unchecked_already_filled(c: &mut Criterion) {
     bench_for_backend::<BenchBuffer>(&mut g);
 }
 
+fn bench_resolve_backend_already_filled(c: &mut Criterion) {
+    let mut g = c.benchmark_group("resolve/backend/already-filled");
+    g.throughput(Throughput::Elements(BENCH_LEN_STRINGS as u64));
+    fn bench_for_backend<'i, BB: BackendBenchmark<'i>>(g: &mut BenchmarkGroup<WallTime>) {
+        g.bench_with_input(
+            BB::NAME,
+            &(BENCH_LEN_STRINGS, BENCH_STRING_LEN),
+            |bencher, &(len_words, word_len)| {
+                let words = generate_test_strings(len_words, word_len);
+                bencher.iter_batched_ref(
+                    || {
+                        let mut backend = <BB::Backend>::default();
+                        let word_ids = words
+                            .iter()
+                            .map(|word| backend.intern(word))
+                            .collect::<Vec<_>>();
+                        (backend, word_ids)
+                    },
+                    |(backend, word_ids)| {
+                        for &word_id in &*word_ids {
+                            black_box(backend.resolve(word_id));
+                        }
+                    },
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+    bench_for_backend::<BenchBucket>(&mut g);
+    bench_for_backend::<BenchString>(&mut g);
+    bench_for_backend::<BenchBuffer>(&mut g);
+}
+
 fn bench_get_already_filled(c: &mut Criterion) {
     let mut g = c.benchmark_group("get/already-filled");
     g.throughput(Throughput::Elements(BENCH_LEN_STRINGS as u64));
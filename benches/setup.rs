@@ -78,7 +78,7 @@ pub const BENCH_LEN_STRINGS: usize = 100_000;
 pub const BENCH_STRING_LEN: usize = 5;
 
 type FxBuildHasher = fxhash::FxBuildHasher;
-type StringInternerWith<'i, B> = StringInterner<'i, B, FxBuildHasher>;
+pub type StringInternerWith<'i, B> = StringInterner<'i, B, FxBuildHasher>;
 
 pub trait BackendBenchmark<'i> {
     const NAME: &'static str;
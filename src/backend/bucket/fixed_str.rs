@@ -1,5 +1,6 @@
 use super::InternedStr;
-use alloc::string::String;
+use crate::error::ExceedsCapacityError;
+use alloc::{collections::TryReserveError, string::String};
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct FixedString {
@@ -15,6 +16,24 @@ impl FixedString {
         }
     }
 
+    /// Creates a new fixed string with the given fixed capacity.
+    ///
+    /// Returns an error instead of aborting if the allocation fails.
+    #[inline]
+    pub fn try_with_capacity(cap: usize) -> Result<Self, TryReserveError> {
+        let mut contents = String::new();
+        contents.try_reserve_exact(cap)?;
+        Ok(Self { contents })
+    }
+
+    /// Reopens a string previously returned by [`FixedString::finish`].
+    ///
+    /// The capacity of `contents` becomes the fixed capacity.
+    #[inline]
+    pub fn reopen(contents: String) -> Self {
+        Self { contents }
+    }
+
     /// Returns the underlying [`Box<str>`].
     ///
     /// Guarantees not to perform any reallocations in this process.
@@ -25,6 +44,18 @@ impl FixedString {
         self.contents
     }
 
+    /// Shortens the fixed string to the given length in bytes.
+    ///
+    /// Does nothing if `len` is greater than the current length.
+    ///
+    /// # Panics
+    ///
+    /// If `len` does not lie on a `char` boundary.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        self.contents.truncate(len)
+    }
+
     /// Returns the capacity in bytes of the fixed string.
     #[inline]
     pub fn capacity(&self) -> usize {
@@ -37,24 +68,236 @@ impl FixedString {
         self.contents.len()
     }
 
+    /// Returns the filled part of the fixed string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.contents.as_str()
+    }
+
+    /// Returns a reference to the contents of the fixed string starting at byte `start`.
+    ///
+    /// # Panics
+    ///
+    /// If `start` is out of bounds or does not lie on a `char` boundary.
+    #[inline]
+    pub fn interned_from(&self, start: usize) -> InternedStr {
+        // SAFETY: The bytes are never moved since a fixed string never reallocates.
+        unsafe { InternedStr::new(&self.contents[start..]) }
+    }
+
+    /// Returns a reference to the `len` bytes of the fixed string starting at byte `start`.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds or does not lie on `char` boundaries.
+    #[inline]
+    pub fn interned_at(&self, start: usize, len: usize) -> InternedStr {
+        // SAFETY: The bytes are never moved since a fixed string never reallocates.
+        unsafe { InternedStr::new(&self.contents[start..start + len]) }
+    }
+
+    /// Shrinks the capacity of the fixed string to its length.
+    ///
+    /// This reallocates the contents and thus invalidates all references
+    /// previously returned by the fixed string. Returns `true` if the capacity
+    /// was reduced.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) -> bool {
+        let capacity = self.capacity();
+        self.contents.shrink_to_fit();
+        self.capacity() != capacity
+    }
+
+    /// Returns an exclusive reference to the contents of the fixed string
+    /// starting at byte `start`.
+    ///
+    /// References previously returned for these bytes must be recreated with
+    /// [`FixedString::interned_from`] after the mutation.
+    ///
+    /// # Panics
+    ///
+    /// If `start` is out of bounds or does not lie on a `char` boundary.
+    #[inline]
+    pub fn tail_mut(&mut self, start: usize) -> &mut str {
+        &mut self.contents[start..]
+    }
+
     /// Pushes the given string into the fixed string if there is enough capacity.
     ///
     /// Returns a reference to the pushed string if there was enough capacity to
     /// perform the operation. Otherwise returns `None`.
     #[inline]
     pub fn push_str(&mut self, string: &str) -> Option<InternedStr> {
+        self.push_str_at(string).ok().map(|(interned, _)| interned)
+    }
+
+    /// Pushes the given string into the fixed string if there is enough capacity.
+    ///
+    /// Returns a reference to the pushed string together with the byte offset
+    /// within the fixed string at which it starts if there was enough capacity
+    /// to perform the operation.
+    ///
+    /// # Errors
+    ///
+    /// If the string does not fit into the remaining capacity.
+    #[inline]
+    pub fn push_str_at(
+        &mut self,
+        string: &str,
+    ) -> Result<(InternedStr, usize), ExceedsCapacityError> {
         let len = self.len();
-        if self.capacity() < len + string.len() {
-            return None;
+        // The capacity is owned by `contents` which handles allocation failure
+        // and zero-sized buckets itself, so a full check is all that is needed
+        // to guarantee that pushing never reallocates.
+        let remaining = self.capacity() - len;
+        if remaining < string.len() {
+            return Err(ExceedsCapacityError::new(string.len(), remaining));
         }
         self.contents.push_str(string);
         debug_assert_eq!(self.contents.len(), len + string.len());
-        Some(InternedStr::new(
-            // SAFETY: We convert from bytes to utf8 from which we know through the
-            //         input string that they must represent valid utf8.
-            unsafe {
-                core::str::from_utf8_unchecked(&self.contents.as_bytes()[len..len + string.len()])
-            },
-        ))
+        // SAFETY: We convert from bytes to utf8 from which we know through the
+        //         input string that they must represent valid utf8. The bytes
+        //         are never moved since a fixed string never reallocates.
+        let interned = unsafe {
+            InternedStr::new(core::str::from_utf8_unchecked(
+                &self.contents.as_bytes()[len..len + string.len()],
+            ))
+        };
+        Ok((interned, len))
+    }
+
+    /// Pushes the given string into the fixed string starting at an address
+    /// aligned to `align` bytes if there is enough capacity.
+    ///
+    /// The gap in front of the string is filled with zero bytes which count
+    /// towards the capacity. Returns a reference to the pushed string if there
+    /// was enough capacity to perform the operation.
+    ///
+    /// # Errors
+    ///
+    /// If the padding and the string do not fit into the remaining capacity.
+    ///
+    /// # Panics
+    ///
+    /// If `align` is not a power of two.
+    pub fn push_str_aligned(
+        &mut self,
+        string: &str,
+        align: usize,
+    ) -> Result<InternedStr, ExceedsCapacityError> {
+        assert!(align.is_power_of_two(), "alignment must be a power of two");
+        let end = self.contents.as_ptr() as usize + self.len();
+        let padding = end.wrapping_neg() & (align - 1);
+        let remaining = self.capacity() - self.len();
+        if remaining < padding || remaining - padding < string.len() {
+            return Err(ExceedsCapacityError::new(
+                padding.saturating_add(string.len()),
+                remaining,
+            ));
+        }
+        for _ in 0..padding {
+            self.contents.push('\0');
+        }
+        let interned = self.push_str(string);
+        Ok(interned.expect("encountered invalid capacity"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_str_at_works() {
+        let mut fixed = FixedString::with_capacity(10);
+        let (aa, offset_aa) = fixed.push_str_at("aa").unwrap();
+        let (bbb, offset_bbb) = fixed.push_str_at("bbb").unwrap();
+        assert_eq!((aa.as_str(), offset_aa), ("aa", 0));
+        assert_eq!((bbb.as_str(), offset_bbb), ("bbb", 2));
+        assert_eq!(fixed.len(), 5);
+    }
+
+    #[test]
+    fn push_str_aligned_works() {
+        let mut fixed = FixedString::with_capacity(64);
+        fixed.push_str("a").unwrap();
+        let aligned = fixed.push_str_aligned("bb", 8).unwrap();
+        assert_eq!(aligned.as_str(), "bb");
+        assert_eq!(aligned.as_str().as_ptr() as usize % 8, 0);
+        assert!(fixed.as_str()[1..].trim_end_matches("bb").bytes().all(|b| b == 0));
+        let unaligned = fixed.push_str_aligned("c", 1).unwrap();
+        assert_eq!(unaligned.as_str().as_ptr() as usize, aligned.as_str().as_ptr() as usize + 2);
+    }
+
+    #[test]
+    fn push_str_aligned_respects_capacity() {
+        let mut fixed = FixedString::with_capacity(4);
+        let capacity = fixed.capacity();
+        let error = fixed.push_str_aligned(&"a".repeat(capacity + 1), 1).unwrap_err();
+        assert_eq!((error.string_len(), error.capacity()), (capacity + 1, capacity));
+        assert_eq!(fixed.len(), 0);
+    }
+
+    #[test]
+    fn push_str_respects_capacity() {
+        let mut empty = FixedString::default();
+        assert_eq!(empty.capacity(), 0);
+        assert_eq!(empty.push_str("").map(|s| s.as_str().len()), Some(0));
+        assert!(empty.push_str("a").is_none());
+        let mut fixed = FixedString::with_capacity(4);
+        let capacity = fixed.capacity();
+        assert!(fixed.push_str(&"a".repeat(capacity)).is_some());
+        assert!(fixed.push_str("a").is_none());
+        assert_eq!(fixed.capacity(), capacity);
+    }
+
+    #[test]
+    fn eq_compares_contents() {
+        let mut small = FixedString::with_capacity(4);
+        let mut large = FixedString::with_capacity(64);
+        assert_eq!(small, large);
+        small.push_str("ab").unwrap();
+        large.push_str("ab").unwrap();
+        assert_ne!(small.capacity(), large.capacity());
+        assert_eq!(small, large);
+        large.push_str("c").unwrap();
+        assert_ne!(small, large);
+        large.truncate(2);
+        assert_eq!(small.clone().finish(), large.clone().finish());
+        assert_eq!(small, large);
+    }
+
+    #[test]
+    fn failed_push_str_writes_nothing() {
+        let mut fixed = FixedString::with_capacity(8);
+        let capacity = fixed.capacity();
+        let aa = fixed.push_str("aa").unwrap();
+        let ptr = fixed.as_str().as_ptr();
+        assert!(fixed.push_str(&"b".repeat(capacity - 1)).is_none());
+        let error = fixed.push_str_at(&"b".repeat(capacity)).unwrap_err();
+        assert_eq!(error, ExceedsCapacityError::new(capacity, capacity - 2));
+        assert_eq!(fixed.len(), 2);
+        assert_eq!(fixed.capacity(), capacity);
+        assert_eq!(fixed.as_str(), "aa");
+        assert_eq!(fixed.as_str().as_ptr(), ptr);
+        let (cc, offset) = fixed.push_str_at("cc").unwrap();
+        assert_eq!((cc.as_str(), offset), ("cc", 2));
+        assert_eq!(aa.as_str(), "aa");
+        assert_eq!(fixed.as_str(), "aacc");
+    }
+
+    #[test]
+    fn failed_push_str_aligned_writes_no_padding() {
+        let mut fixed = FixedString::with_capacity(16);
+        let capacity = fixed.capacity();
+        fixed.push_str("a").unwrap();
+        let error = fixed.push_str_aligned(&"b".repeat(capacity), 8).unwrap_err();
+        assert_eq!(error.capacity(), capacity - 1);
+        assert_eq!(fixed.len(), 1);
+        assert_eq!(fixed.capacity(), capacity);
+        assert_eq!(fixed.as_str(), "a");
+        let bb = fixed.push_str_aligned("bb", 1).unwrap();
+        assert_eq!(bb.as_str(), "bb");
+        assert_eq!(fixed.as_str(), "abb");
     }
 }
@@ -1,11 +1,21 @@
 #![cfg(feature = "backends")]
 
-use core::ptr::NonNull;
+use alloc::string::String;
+use core::{
+    hash::{Hash, Hasher},
+    ptr::NonNull,
+};
 
 /// Reference to an interned string.
 ///
 /// It is inherently `unsafe` to use instances of this type and should not be
 /// done outside of the `string-interner` crate itself.
+///
+/// # Equality
+///
+/// Equality and hashing are based on the contents of the referenced string and
+/// not on its address. Two interned strings referring to different copies of
+/// the same contents are equal and hash identically.
 #[derive(Debug)]
 #[repr(transparent)]
 pub struct InternedStr {
@@ -14,8 +24,16 @@ pub struct InternedStr {
 
 impl InternedStr {
     /// Creates a new interned string from the given `str`.
+    ///
+    /// # Safety
+    ///
+    /// The caller has to make sure that `val` stays alive and is not moved in
+    /// memory for as long as the returned interned string is used. Within this
+    /// crate this holds for `'static` strings and for strings stored in the
+    /// buckets of a [`BucketBackend`](super::BucketBackend) since their
+    /// contents are never reallocated and outlive the spans referring to them.
     #[inline]
-    pub fn new(val: &str) -> Self {
+    pub unsafe fn new(val: &str) -> Self {
         InternedStr {
             ptr: NonNull::from(val),
         }
@@ -23,9 +41,11 @@ impl InternedStr {
 
     /// Returns a shared reference to the underlying string.
     ///
-    /// # Safety
-    ///
-    /// The user has to make sure that no lifetime guarantees are invalidated.
+    /// The returned reference is bound to the lifetime of `self` rather than
+    /// to the lifetime of the referenced data. This is only sound because
+    /// [`InternedStr::new`] requires the data to outlive the interned string,
+    /// which the bucket backend guarantees by never reallocating its buckets
+    /// and by dropping its spans together with the buckets.
     #[inline]
     pub(super) fn as_str(&self) -> &str {
         // SAFETY: This is safe since we only ever operate on interned `str`
@@ -33,6 +53,23 @@ impl InternedStr {
         //         references.
         unsafe { self.ptr.as_ref() }
     }
+
+    /// Returns the bytes of the underlying string.
+    ///
+    /// See [`InternedStr::as_str`] for the lifetime of the returned reference.
+    #[inline]
+    pub(super) fn as_bytes(&self) -> &[u8] {
+        self.as_str().as_bytes()
+    }
+
+    /// Returns an owned copy of the underlying string.
+    ///
+    /// Use this to detach the string from the storage it refers to, for
+    /// example before the storage is reused.
+    #[inline]
+    pub(super) fn to_owned(&self) -> String {
+        String::from(self.as_str())
+    }
 }
 
 impl Eq for InternedStr {}
@@ -44,6 +81,13 @@ impl PartialEq for InternedStr {
     }
 }
 
+impl Hash for InternedStr {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -53,4 +97,25 @@ mod tests {
         use core::mem;
         assert_eq!(mem::size_of::<InternedStr>(), mem::size_of::<&str>());
     }
+
+    #[test]
+    fn eq_and_hash_use_contents() {
+        use core::hash::BuildHasher;
+        use hashbrown::DefaultHashBuilder;
+
+        let owned = String::from("hello");
+        // SAFETY: Both strings outlive the interned strings referring to them.
+        let (lhs, rhs) = unsafe { (InternedStr::new("hello"), InternedStr::new(&owned)) };
+        assert_ne!(lhs.ptr, rhs.ptr);
+        assert_eq!(lhs, rhs);
+        let builder = DefaultHashBuilder::default();
+        assert_eq!(builder.hash_one(&lhs), builder.hash_one(&rhs));
+    }
+
+    #[test]
+    fn to_owned_works() {
+        // SAFETY: `'static` strings are never deallocated or moved.
+        let interned = unsafe { InternedStr::new("hello") };
+        assert_eq!(interned.to_owned(), "hello");
+    }
 }
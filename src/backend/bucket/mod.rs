@@ -2,12 +2,64 @@
 
 mod fixed_str;
 mod interned_str;
+mod par_iter;
 
+#[cfg(feature = "rayon")]
+pub use self::par_iter::ParIter;
 use self::{fixed_str::FixedString, interned_str::InternedStr};
-use super::{Backend, PhantomBackend};
-use crate::{symbol::expect_valid_symbol, DefaultSymbol, Symbol};
-use alloc::{string::String, vec::Vec};
-use core::{iter::Enumerate, marker::PhantomData, slice};
+use super::{AccessKind, Backend, PhantomBackend, RebindSymbol};
+use crate::{
+    error::ExceedsCapacityError,
+    symbol::expect_valid_symbol,
+    CapacityReport,
+    DefaultSymbol,
+    Symbol,
+};
+use alloc::{boxed::Box, collections::TryReserveError, string::String, vec::Vec};
+use core::{
+    fmt,
+    fmt::{Debug, Formatter},
+    iter::Enumerate,
+    marker::PhantomData,
+    ops::Range,
+    slice,
+};
+
+/// The number of spans stored inline with the `smallvec-spans` feature.
+#[cfg(feature = "smallvec-spans")]
+const INLINE_SPANS: usize = 8;
+
+/// The maximum capacity in bytes of a single bucket.
+///
+/// Allocations must not exceed `isize::MAX` bytes.
+const MAX_BUCKET_CAPACITY: usize = isize::MAX as usize;
+
+/// Returns an error if a string of `len` bytes does not fit into any bucket.
+#[inline]
+fn check_bucket_capacity(len: usize) -> Result<(), ExceedsCapacityError> {
+    if len > MAX_BUCKET_CAPACITY {
+        return Err(ExceedsCapacityError::new(len, MAX_BUCKET_CAPACITY));
+    }
+    Ok(())
+}
+
+/// Panics with a descriptive message if `bytes` exceeds [`MAX_BUCKET_CAPACITY`].
+#[inline]
+fn assert_bucket_capacity(bytes: usize) {
+    assert!(
+        bytes <= MAX_BUCKET_CAPACITY,
+        "bucket capacity of {} bytes exceeds the maximum of isize::MAX bytes",
+        bytes,
+    );
+}
+
+/// The storage of the spans of a [`BucketBackend`].
+#[cfg(feature = "smallvec-spans")]
+type Spans = smallvec::SmallVec<[InternedStr; INLINE_SPANS]>;
+
+/// The storage of the spans of a [`BucketBackend`].
+#[cfg(not(feature = "smallvec-spans"))]
+type Spans = Vec<InternedStr>;
 
 /// An interner backend that reduces memory allocations by using buckets.
 /// 
@@ -31,17 +83,67 @@ use core::{iter::Enumerate, marker::PhantomData, slice};
 /// This backend is ideal when interned strings must remain valid even after new ones are
 /// added.general use
 /// 
+/// ## Lifetimes
+/// Resolved strings borrow the backend and cannot outlive it even though their
+/// contents are never moved by interning new strings. The buckets are owned by
+/// the backend and freed when it is dropped, and operations such as
+/// [`BucketBackend::rollback`] or [`BucketBackend::shrink_unused`] free or move
+/// the contents of buckets. Only strings added via
+/// [`BucketBackend::from_borrowed`] are owned elsewhere, resolve them from the
+/// borrowed data itself if they need to outlive the backend.
+/// 
+/// ## Small interners
+/// The backend does not store its first bucket inline. Spans are raw pointers
+/// into the buckets and the backend, like the [`StringInterner`][crate::StringInterner]
+/// owning it, can be moved freely, which would leave spans into an inline buffer
+/// dangling. Boxing that buffer to pin it would allocate just like the first
+/// heap bucket does. Instead an empty backend does not allocate at all, the
+/// first bucket is only allocated once the first string is interned, and the
+/// `smallvec-spans` feature keeps the spans of small interners inline.
+/// 
 /// Refer to the [comparison table][crate::_docs::comparison_table] for comparison with
 /// other backends.
 /// 
 /// [matklad's blog post]:
 ///     https://matklad.github.io/2020/03/22/fast-simple-rust-interner.html
-#[derive(Debug)]
 pub struct BucketBackend<'i, S: Symbol = DefaultSymbol> {
-    spans: Vec<InternedStr>,
+    spans: Spans,
     head: FixedString,
     full: Vec<String>,
+    group_heads: Vec<FixedString>,
+    group_full: Vec<String>,
     marker: PhantomBackend<'i, Self>,
+    on_grow: Option<GrowHook>,
+    fixed_bucket_size: Option<usize>,
+    bounded_growth: bool,
+}
+
+/// Callback invoked whenever a [`BucketBackend`] allocates a new bucket.
+///
+/// Receives the requested and the allocated capacity in bytes.
+pub type GrowHook = Box<dyn Fn(usize, usize) + Send + Sync>;
+
+/// Identifies a group of strings that are stored next to each other, see
+/// [`BucketBackend::intern_grouped`].
+pub type GroupId = u16;
+
+impl<'i, S> Debug for BucketBackend<'i, S>
+where
+    S: Symbol,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BucketBackend")
+            .field("spans", &self.spans)
+            .field("head", &self.head)
+            .field("full", &self.full)
+            .field("group_heads", &self.group_heads)
+            .field("group_full", &self.group_full)
+            .field("marker", &self.marker)
+            .field("on_grow", &self.on_grow.as_ref().map(|_| "<hook>"))
+            .field("fixed_bucket_size", &self.fixed_bucket_size)
+            .field("bounded_growth", &self.bounded_growth)
+            .finish()
+    }
 }
 
 /// # Safety
@@ -58,14 +160,152 @@ unsafe impl<'i, S> Send for BucketBackend<'i, S> where S: Symbol {}
 /// the bucket backend has no interior mutability.
 unsafe impl<'i, S> Sync for BucketBackend<'i, S> where S: Symbol {}
 
+/// A checkpoint of the state of a [`BucketBackend`].
+///
+/// Created by [`BucketBackend::checkpoint`] and consumed by
+/// [`BucketBackend::rollback`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    spans: usize,
+    head_len: usize,
+    full_len: usize,
+}
+
+impl Checkpoint {
+    /// Returns the number of strings that were interned at the checkpoint.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.spans
+    }
+
+    /// Returns `true` if no strings were interned at the checkpoint.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// The location of an interned string within the buckets of a [`BucketBackend`].
+///
+/// Returned by [`BucketBackend::span_location`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SpanLocation {
+    /// The index of the bucket as yielded by [`BucketBackend::buckets`].
+    pub bucket: usize,
+    /// The byte offset of the string within its bucket.
+    pub offset: usize,
+    /// The length of the string in bytes.
+    pub len: usize,
+}
+
 impl<'i, S: Symbol> Default for BucketBackend<'i, S> {
     #[cfg_attr(feature = "inline-more", inline)]
     fn default() -> Self {
+        Self::with_vec_capacities(Self::DEFAULT_SPAN_CAP, Self::DEFAULT_FULL_CAP)
+    }
+}
+
+impl<'i, S> BucketBackend<'i, S>
+where
+    S: Symbol,
+{
+    /// The number of strings a [`BucketBackend::default`] allocates space for up front.
+    ///
+    /// Defaults to `0` so that an unused backend does not allocate.
+    pub const DEFAULT_SPAN_CAP: usize = 0;
+
+    /// The number of full buckets a [`BucketBackend::default`] allocates space for up front.
+    ///
+    /// Defaults to `0` so that an unused backend does not allocate.
+    pub const DEFAULT_FULL_CAP: usize = 0;
+
+    /// Creates a new backend preset for interning only a handful of strings.
+    ///
+    /// Allocates space for 4 strings and a single full bucket up front.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn tiny() -> Self {
+        Self::with_vec_capacities(4, 1)
+    }
+
+    /// Creates a new backend that allocates all buckets with a capacity of
+    /// `bucket_size` bytes, see [`BucketBackend::set_fixed_bucket_size`].
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_fixed_bucket_size(bucket_size: usize) -> Self {
+        let mut backend = Self::default();
+        backend.set_fixed_bucket_size(Some(bucket_size));
+        backend
+    }
+
+    /// Creates a new backend over the strings at `ranges` within the borrowed `data`.
+    ///
+    /// The strings are not copied but refer directly into `data`, for example
+    /// a persisted blob of strings in a memory-mapped file. Together with an
+    /// index of the string ranges this allows building a read-only interner
+    /// over the blob without copying, see [`StringInterner::from_backend`].
+    /// The symbols are assigned in the order of `ranges`. Strings interned
+    /// afterwards are stored in buckets as usual.
+    ///
+    /// # Ownership
+    ///
+    /// `data` is borrowed for the lifetime `'i` of the backend and is neither
+    /// modified nor freed by it, it is never treated as a bucket of the backend.
+    /// This means:
+    ///
+    /// - The owner of `data` must keep it alive and unmodified until the backend
+    ///   is dropped. For a memory-mapped file this is part of the contract of
+    ///   creating `data` in the first place, including that no other process
+    ///   modifies the file while it is mapped.
+    /// - Neither [`BucketBackend::buckets`] nor [`BucketBackend::into_buckets`]
+    ///   include `data` and the strings within it are reported as not being
+    ///   stored in any bucket, like `'static` strings.
+    /// - Cloning the backend copies the borrowed strings into a bucket of the
+    ///   clone, so clones own all of their strings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::{backend::{Backend, BucketBackend}, DefaultSymbol};
+    /// let blob = String::from("helloworld");
+    /// let backend = BucketBackend::<DefaultSymbol>::from_borrowed(&blob, [0..5, 5..10]);
+    /// let strings = backend.iter().map(|(_, string)| string).collect::<Vec<_>>();
+    /// assert_eq!(strings, ["hello", "world"]);
+    /// assert_eq!(strings[0].as_ptr(), blob.as_ptr());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - If a range is out of bounds of `data` or does not lie on `char` boundaries.
+    /// - If `ranges` yields more strings than the chosen symbol type supports.
+    ///
+    /// [`StringInterner::from_backend`]: crate::StringInterner::from_backend
+    #[track_caller]
+    pub fn from_borrowed<R>(data: &'i str, ranges: R) -> Self
+    where
+        R: IntoIterator<Item = Range<usize>>,
+    {
+        let ranges = ranges.into_iter();
+        let mut backend = Self::with_vec_capacities(ranges.size_hint().0, 0);
+        for range in ranges {
+            // SAFETY: `data` is borrowed for `'i` and thus outlives the backend.
+            let interned = unsafe { InternedStr::new(&data[range]) };
+            let _ = backend.push_span(interned);
+        }
+        backend
+    }
+
+    /// Creates a new backend with space for `spans` strings and `full` full buckets.
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn with_vec_capacities(spans: usize, full: usize) -> Self {
         Self {
-            spans: Vec::new(),
+            spans: Spans::with_capacity(spans),
             head: FixedString::default(),
-            full: Vec::new(),
+            full: Vec::with_capacity(full),
+            group_heads: Vec::new(),
+            group_full: Vec::new(),
             marker: Default::default(),
+            on_grow: None,
+            fixed_bucket_size: None,
+            bounded_growth: false,
         }
     }
 }
@@ -74,6 +314,8 @@ impl<'i, S> Backend<'i> for BucketBackend<'i, S>
 where
     S: Symbol,
 {
+    const ACCESS_KIND: AccessKind = AccessKind::Stable;
+    const SYMBOLS_MONOTONIC: bool = true;
     type Access<'local> = &'local str
     where
         Self: 'local,
@@ -86,43 +328,133 @@ where
 
     #[cfg_attr(feature = "inline-more", inline)]
     fn with_capacity(cap: usize) -> Self {
+        assert_bucket_capacity(cap);
         Self {
-            spans: Vec::with_capacity(cap),
+            spans: Spans::with_capacity(cap),
             head: FixedString::with_capacity(cap),
             full: Vec::new(),
+            group_heads: Vec::new(),
+            group_full: Vec::new(),
             marker: Default::default(),
+            on_grow: None,
+            fixed_bucket_size: None,
+            bounded_growth: false,
+        }
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn with_capacity_and_bytes(cap: usize, bytes: usize) -> Self {
+        assert_bucket_capacity(bytes);
+        Self {
+            spans: Spans::with_capacity(cap),
+            head: FixedString::with_capacity(bytes),
+            full: Vec::new(),
+            group_heads: Vec::new(),
+            group_full: Vec::new(),
+            marker: Default::default(),
+            on_grow: None,
+            fixed_bucket_size: None,
+            bounded_growth: false,
         }
     }
 
     #[inline]
     fn intern(&mut self, string: &str) -> Self::Symbol {
+        // The symbol is created up front so that an exhausted symbol space
+        // panics before any bytes are written into the buckets.
+        let symbol = self.next_symbol();
         // SAFETY: This is safe because we never hand out the returned
         //         interned string instance to the outside and only operate
         //         on it within this backend.
         let interned = unsafe { self.alloc(string) };
-        self.push_span(interned)
+        self.spans.push(interned);
+        symbol
     }
 
     #[cfg_attr(feature = "inline-more", inline)]
     fn intern_static(&mut self, string: &'static str) -> Self::Symbol {
-        let interned = InternedStr::new(string);
+        // SAFETY: `'static` strings are never deallocated or moved.
+        let interned = unsafe { InternedStr::new(string) };
         self.push_span(interned)
     }
 
+    fn reserve(&mut self, additional: usize) {
+        self.spans.reserve(additional);
+        // Only an empty head can be replaced since the buckets must never be
+        // reallocated while spans refer to them.
+        if self.head.len() == 0 && self.head.capacity() < additional {
+            self.head = FixedString::with_capacity(additional);
+        }
+    }
+
+    fn reserve_exact(&mut self, additional: usize, bytes: usize) {
+        self.spans.reserve_exact(additional);
+        if self.head.capacity() - self.head.len() >= bytes {
+            return;
+        }
+        if self.head.len() == 0 {
+            assert_bucket_capacity(bytes);
+            self.head = FixedString::with_capacity(bytes);
+        } else {
+            // Strings cannot span buckets, so the remaining capacity of the
+            // current head is left unused.
+            self.replace_head(bytes, bytes);
+        }
+    }
+
     fn shrink_to_fit(&mut self) {
         self.spans.shrink_to_fit();
         // Commenting out the below line fixes: https://github.com/Robbepop/string-interner/issues/46
         // self.head.shrink_to_fit();
         self.full.shrink_to_fit();
+        self.group_full.shrink_to_fit();
+    }
+
+    fn capacity_report(&self) -> CapacityReport {
+        let full = self.full.iter().chain(&self.group_full);
+        let full_used = full.clone().map(String::len).sum::<usize>();
+        let full_allocated = full.map(String::capacity).sum::<usize>();
+        let heads = self
+            .group_heads
+            .iter()
+            .chain(core::iter::once(&self.head))
+            .filter(|head| head.capacity() != 0);
+        CapacityReport {
+            bytes_used: full_used + heads.clone().map(FixedString::len).sum::<usize>(),
+            bytes_allocated: full_allocated
+                + heads.clone().map(FixedString::capacity).sum::<usize>(),
+            buckets: self.full.len() + self.group_full.len() + heads.count(),
+            spans_capacity: self.spans.capacity(),
+            ..CapacityReport::default()
+        }
     }
 
     #[inline]
     fn resolve(&self, symbol: Self::Symbol) -> Option<&str> {
-        self.spans.get(symbol.to_usize()).map(InternedStr::as_str)
+        // An explicit bounds check results in better code than `Option`
+        // combinators on `self.spans.get`.
+        if symbol.to_usize() < self.spans.len() {
+            // SAFETY: The symbol has just been checked to be in bounds.
+            Some(unsafe { self.resolve_unchecked(symbol) })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn resolve_bytes<'l>(&'l self, symbol: Self::Symbol) -> Option<&'l [u8]>
+    where
+        'i: 'l,
+        Self::Access<'l>: Into<&'l str>,
+    {
+        self.spans
+            .get(symbol.to_usize())
+            .map(InternedStr::as_bytes)
     }
 
     #[inline]
     unsafe fn resolve_unchecked(&self, symbol: Self::Symbol) -> &str {
+        debug_assert!(symbol.to_usize() < self.spans.len(), "symbol out of range");
         // SAFETY: The function is marked unsafe so that the caller guarantees
         //         that required invariants are checked.
         unsafe { self.spans.get_unchecked(symbol.to_usize()).as_str() }
@@ -139,40 +471,870 @@ where
     S: Symbol,
 {
     /// Returns the next available symbol.
+    ///
+    /// # Panics
+    ///
+    /// If the backend already holds `S::MAX_INDEX + 1` strings.
+    #[track_caller]
     fn next_symbol(&self) -> S {
-        expect_valid_symbol(self.spans.len())
+        let index = self.spans.len();
+        assert!(index <= S::MAX_INDEX, "symbol space exhausted");
+        expect_valid_symbol(index)
+    }
+
+    /// Releases the unused capacity of the head bucket.
+    ///
+    /// Reallocates the head bucket to fit its contents and updates the spans
+    /// of the strings stored in it. The next string that is interned then
+    /// allocates a new head bucket. Returns `true` if memory was released.
+    ///
+    /// Unlike [`Backend::shrink_to_fit`] this moves interned strings in memory
+    /// and visits all spans.
+    ///
+    /// # Note
+    ///
+    /// The strings of the head bucket are moved, so strings of this backend
+    /// borrowed by other backends via [`BucketBackend::intern_borrowed`] become
+    /// dangling, and strings interned by [`BucketBackend::intern_aligned`] may
+    /// lose their alignment.
+    pub fn shrink_unused(&mut self) -> bool {
+        if self.head.len() == self.head.capacity() {
+            return false;
+        }
+        let head = self.head.as_str();
+        let head_start = head.as_ptr() as usize;
+        let head_range = head_start..head_start + head.len();
+        let in_head = self
+            .spans
+            .iter()
+            .enumerate()
+            .filter_map(|(index, span)| {
+                let string = span.as_str();
+                let start = string.as_ptr() as usize;
+                let in_head = head_range.contains(&start)
+                    || (string.is_empty() && start == head_range.end);
+                in_head.then(|| (index, start - head_start, string.len()))
+            })
+            .collect::<Vec<_>>();
+        if !self.head.shrink_to_fit() {
+            return false;
+        }
+        for (index, start, len) in in_head {
+            self.spans[index] = self.head.interned_at(start, len);
+        }
+        true
+    }
+
+    /// Moves all strings into a single bucket that fits them exactly.
+    ///
+    /// Performs the same flattening as [`Clone::clone`] but in place: all
+    /// strings, including `'static` and borrowed ones, are copied into one new
+    /// bucket, their spans are updated and all previous buckets are freed.
+    /// Symbols remain valid. Use this to minimize the number of allocations
+    /// and the allocated bytes of a backend that is not going to change anymore.
+    ///
+    /// # Note
+    ///
+    /// This copies the contents of all strings once, which takes time linear in
+    /// their total length. The strings are moved, so strings of this backend
+    /// borrowed by other backends via [`BucketBackend::intern_borrowed`] become
+    /// dangling, strings interned by [`BucketBackend::intern_aligned`] lose
+    /// their alignment, and checkpoints created before the compaction must no
+    /// longer be used for [`BucketBackend::rollback`].
+    pub fn compact_storage(&mut self) {
+        let len_strings = self
+            .spans
+            .iter()
+            .fold(0, |lhs, rhs| lhs + rhs.as_str().len());
+        let mut head = FixedString::with_capacity(len_strings);
+        for span in &mut self.spans {
+            let interned = head
+                .push_str(span.as_str())
+                .expect("encountered invalid head capacity");
+            *span = interned;
+        }
+        self.spans.shrink_to_fit();
+        self.head = head;
+        self.full = Vec::new();
+        self.group_heads = Vec::new();
+        self.group_full = Vec::new();
+    }
+
+    /// Calls `f` with an exclusive reference to the most recently interned string.
+    ///
+    /// This allows in-place modifications such as ASCII case folding of the
+    /// string that has just been interned. Only the most recently interned
+    /// string can be mutated since it is the only one guaranteed not to share
+    /// its bytes with any other string.
+    ///
+    /// Returns `None` without calling `f` if no string has been interned or
+    /// the most recently interned string is not stored in a bucket, e.g.
+    /// because it is a `'static` string.
+    ///
+    /// # Note
+    ///
+    /// The backend does not deduplicate so mutating a string is sound, but the
+    /// symbol now resolves to the mutated string.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::backend::{Backend, BucketBackend};
+    /// let mut backend = <BucketBackend>::default();
+    /// let symbol = backend.intern("Hello");
+    /// backend.with_last_mut(str::make_ascii_lowercase);
+    /// assert_eq!(backend.resolve(symbol), Some("hello"));
+    /// ```
+    pub fn with_last_mut<R>(&mut self, f: impl FnOnce(&mut str) -> R) -> Option<R> {
+        let last = self.spans.last()?.as_str();
+        let head = self.head.as_str();
+        let start = (last.as_ptr() as usize).checked_sub(head.as_ptr() as usize)?;
+        if start + last.len() != head.len() {
+            return None;
+        }
+        let result = f(self.head.tail_mut(start));
+        // The mutation invalidated the reference of the span, so it is recreated.
+        let span = self.spans.last_mut().expect("encountered missing span");
+        *span = self.head.interned_from(start);
+        Some(result)
+    }
+
+    /// Interns the given string at an address aligned to `align` bytes.
+    ///
+    /// Returns a symbol for resolution into the original string. The gap in
+    /// front of the string is filled with zero bytes which are part of the
+    /// [`buckets`](BucketBackend::buckets) contents but not of any string.
+    /// Does not deduplicate.
+    ///
+    /// # Note
+    ///
+    /// The alignment is only guaranteed as long as the string is resolved
+    /// through this backend and stays where it was interned.
+    /// [`Clone`] and [`BucketBackend::compact_storage`] pack all strings
+    /// tightly into a single bucket without the padding, and
+    /// [`BucketBackend::shrink_unused`] moves the strings of the head bucket
+    /// to a new allocation. None of them preserve the alignment.
+    ///
+    /// # Panics
+    ///
+    /// - If `align` is not a power of two.
+    /// - If the backend already holds the maximum number of strings possible
+    ///   by the chosen symbol type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::backend::{Backend, BucketBackend};
+    /// let mut backend = <BucketBackend>::default();
+    /// let _ = backend.intern("a");
+    /// let aligned = backend.intern_aligned("bbbb", 4);
+    /// assert_eq!(backend.resolve(aligned).unwrap().as_ptr() as usize % 4, 0);
+    /// ```
+    #[must_use = "the symbol is the only way to resolve the interned string"]
+    #[track_caller]
+    pub fn intern_aligned(&mut self, string: &str, align: usize) -> S {
+        let symbol = self.next_symbol();
+        let interned = match self.head.push_str_aligned(string, align) {
+            Ok(interned) => interned,
+            Err(_) => {
+                self.new_head(string.len().saturating_add(align - 1));
+                self.head
+                    .push_str_aligned(string, align)
+                    .expect("encountered invalid head capacity")
+            }
+        };
+        self.spans.push(interned);
+        symbol
+    }
+
+    /// Interns the given string unless it is too large for any bucket.
+    ///
+    /// Like [`Backend::intern`] but returns an error instead of panicking if
+    /// the string exceeds the maximum bucket capacity of `isize::MAX` bytes.
+    ///
+    /// # Panics
+    ///
+    /// If the backend already holds the maximum number of strings possible
+    /// by the chosen symbol type.
+    #[track_caller]
+    pub fn try_intern(&mut self, string: &str) -> Result<S, ExceedsCapacityError> {
+        check_bucket_capacity(string.len())?;
+        Ok(self.intern(string))
     }
 
     /// Pushes the given interned string into the spans and returns its symbol.
+    #[track_caller]
     fn push_span(&mut self, interned: InternedStr) -> S {
         let symbol = self.next_symbol();
         self.spans.push(interned);
         symbol
     }
 
-    /// Interns a new string into the backend and returns a reference to it.
-    unsafe fn alloc(&mut self, string: &str) -> InternedStr {
-        let cap = self.head.capacity();
-        if cap < self.head.len() + string.len() {
-            let new_cap = (usize::max(cap, string.len()) + 1).next_power_of_two();
-            let new_head = FixedString::with_capacity(new_cap);
-            let old_head = core::mem::replace(&mut self.head, new_head);
+    /// Interns the given string without copying its contents into a bucket.
+    ///
+    /// Returns a symbol for resolution into the original string. This allows
+    /// zero-copy merging of interners by registering the strings of another
+    /// interner, see [`StringInterner::get_or_intern_borrowed`].
+    ///
+    /// # Safety
+    ///
+    /// The contents of `string` must stay alive and must not be moved or
+    /// mutated for as long as this backend or any symbol resolved through it
+    /// is in use. For strings of another [`BucketBackend`] this holds as long
+    /// as the other backend outlives this one and is neither rolled back nor
+    /// otherwise modified in a way that frees its buckets.
+    ///
+    /// # Panics
+    ///
+    /// If the backend already holds the maximum number of strings possible
+    /// by the chosen symbol type.
+    ///
+    /// [`StringInterner::get_or_intern_borrowed`]: crate::StringInterner::get_or_intern_borrowed
+    #[must_use = "the symbol is the only way to resolve the interned string"]
+    #[inline]
+    #[track_caller]
+    pub unsafe fn intern_borrowed(&mut self, string: &str) -> S {
+        // SAFETY: The caller guarantees that `string` outlives this backend.
+        let interned = unsafe { InternedStr::new(string) };
+        self.push_span(interned)
+    }
+
+    /// Interns `string` into the head bucket of `group` and returns its symbol.
+    ///
+    /// Every group has its own head bucket so that strings of the same group
+    /// are stored next to each other, independent of the strings interned in
+    /// between. This improves locality when the strings of a group are
+    /// resolved together. Does not deduplicate, see
+    /// [`StringInterner::get_or_intern_grouped`] for that.
+    ///
+    /// # Note
+    ///
+    /// Full buckets of groups are kept apart from the other buckets and are
+    /// never freed by [`BucketBackend::rollback`], so rolling back grouped
+    /// strings discards their symbols but keeps their bytes.
+    ///
+    /// # Panics
+    ///
+    /// If the backend already holds the maximum number of strings possible
+    /// by the chosen symbol type.
+    ///
+    /// [`StringInterner::get_or_intern_grouped`]: crate::StringInterner::get_or_intern_grouped
+    #[must_use = "the symbol is the only way to resolve the interned string"]
+    #[track_caller]
+    pub fn intern_grouped(&mut self, group: GroupId, string: &str) -> S {
+        // Check for symbol exhaustion before any bytes are written.
+        let _ = self.next_symbol();
+        let index = usize::from(group);
+        if index >= self.group_heads.len() {
+            self.group_heads.resize_with(index + 1, FixedString::default);
+        }
+        let head = &self.group_heads[index];
+        if head.capacity() - head.len() < string.len() {
+            let cap = self.next_bucket_capacity(head.capacity(), string.len());
+            assert_bucket_capacity(cap);
+            if let Some(on_grow) = &self.on_grow {
+                on_grow(string.len(), cap);
+            }
+            let new_head = FixedString::with_capacity(cap);
+            let old_head = core::mem::replace(&mut self.group_heads[index], new_head);
+            // Full group buckets must survive rollbacks of the other buckets.
+            if old_head.capacity() != 0 {
+                self.group_full.push(old_head.finish());
+            }
+        }
+        let interned = self.group_heads[index]
+            .push_str(string)
+            .expect("encountered invalid group head capacity");
+        self.push_span(interned)
+    }
+
+    /// Returns a checkpoint of the current state of the backend.
+    ///
+    /// Use [`BucketBackend::rollback`] to discard all strings interned after it.
+    #[inline]
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            spans: self.spans.len(),
+            head_len: self.head.len(),
+            full_len: self.full.len(),
+        }
+    }
+
+    /// Discards all strings interned since the given checkpoint.
+    ///
+    /// Buckets allocated after the checkpoint are freed and the bucket that was
+    /// the head at the time of the checkpoint becomes the head again.
+    ///
+    /// # Safety
+    ///
+    /// - `checkpoint` must have been created by [`BucketBackend::checkpoint`] of
+    ///   this backend.
+    /// - The backend must not have been rolled back to an earlier checkpoint
+    ///   in the meantime.
+    ///
+    /// All symbols created after the checkpoint become invalid and must no
+    /// longer be used.
+    pub unsafe fn rollback(&mut self, checkpoint: Checkpoint) {
+        debug_assert!(checkpoint.spans <= self.spans.len());
+        debug_assert!(checkpoint.full_len <= self.full.len());
+        self.spans.truncate(checkpoint.spans);
+        if checkpoint.full_len < self.full.len() {
+            // The head at the time of the checkpoint has been moved into the
+            // full buckets since, all later buckets are no longer referenced.
+            self.full.truncate(checkpoint.full_len + 1);
+            let old_head = self.full.pop().expect("encountered missing bucket");
+            self.head = FixedString::reopen(old_head);
+        }
+        debug_assert!(checkpoint.head_len <= self.head.len());
+        self.head.truncate(checkpoint.head_len);
+    }
+
+    /// Removes the string of `symbol` and moves the last string into its place.
+    ///
+    /// Returns the removed string or `None` if the symbol is invalid. After the
+    /// removal the symbol resolves to the string of the former last symbol,
+    /// which becomes invalid itself.
+    ///
+    /// # Note
+    ///
+    /// The bytes of the removed string remain in their bucket until the backend
+    /// is dropped. Checkpoints created before the removal must no longer be
+    /// used for [`BucketBackend::rollback`].
+    pub fn swap_remove(&mut self, symbol: S) -> Option<String> {
+        let index = symbol.to_usize();
+        if index >= self.spans.len() {
+            return None;
+        }
+        let removed = self.spans.swap_remove(index);
+        Some(removed.to_owned())
+    }
+
+    /// Consumes the backend and returns its buckets without freeing them.
+    ///
+    /// The buckets are returned in the order of [`BucketBackend::buckets`] with
+    /// the current head bucket being finalized and returned last. All spans are
+    /// dropped so the returned buckets can safely be reused, for example by
+    /// clearing them and handing them back to an allocation pool.
+    ///
+    /// # Note
+    ///
+    /// Heads are only returned if they have allocated any capacity.
+    pub fn into_buckets(self) -> Vec<String> {
+        let Self {
+            spans,
+            head,
+            mut full,
+            group_heads,
+            group_full,
+            ..
+        } = self;
+        drop(spans);
+        full.extend(group_full);
+        full.extend(
+            group_heads
+                .into_iter()
+                .chain(core::iter::once(head))
+                .filter(|head| head.capacity() != 0)
+                .map(FixedString::finish),
+        );
+        full
+    }
+
+    /// Returns the filled contents of all buckets, the head being last.
+    ///
+    /// Every bucket is yielded as a `str` so that its contents can be scanned with
+    /// [`str::bytes`] or [`str::chars`]. `'static` strings are never stored in
+    /// buckets and the head is yielded even if it is empty. The buckets of
+    /// [grouped][BucketBackend::intern_grouped] strings are yielded after the
+    /// full buckets and before the head.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::backend::{Backend, BucketBackend};
+    /// let mut backend = <BucketBackend>::default();
+    /// let _ = backend.intern("aa");
+    /// let _ = backend.intern("bb");
+    /// let bytes = backend.buckets().flat_map(str::bytes).count();
+    /// assert_eq!(bytes, 4);
+    /// ```
+    pub fn buckets(&self) -> impl Iterator<Item = &str> {
+        self.full
+            .iter()
+            .chain(&self.group_full)
+            .map(String::as_str)
+            .chain(
+                self.group_heads
+                    .iter()
+                    .filter(|head| head.capacity() != 0)
+                    .map(FixedString::as_str),
+            )
+            .chain(core::iter::once(self.head.as_str()))
+    }
+
+    /// Returns an iterator over the strings stored in the bucket at `bucket_index`.
+    ///
+    /// Buckets are indexed in allocation order with the current head bucket being
+    /// last. The strings are yielded in symbol order. `'static` strings are never
+    /// stored in buckets and an out of bounds `bucket_index` yields no strings.
+    pub fn strings_in_bucket(&self, bucket_index: usize) -> impl Iterator<Item = &str> {
+        let range = self.buckets().nth(bucket_index).map_or(0..0, |bucket| {
+            let start = bucket.as_ptr() as usize;
+            start..start + bucket.len()
+        });
+        self.spans
+            .iter()
+            .map(InternedStr::as_str)
+            .filter(move |string| range.contains(&(string.as_ptr() as usize)))
+    }
+
+    /// Returns the location of the string for the given `symbol` within the buckets.
+    ///
+    /// Together with the contents of [`BucketBackend::buckets`] this allows to
+    /// persist the backend as bucket blobs plus an index of locations.
+    ///
+    /// Returns `None` if the symbol is invalid or refers to a `'static` string
+    /// which is not stored in any bucket.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::backend::{Backend, BucketBackend};
+    /// let mut backend = <BucketBackend>::default();
+    /// let _ = backend.intern("aa");
+    /// let bb = backend.intern("bb");
+    /// let location = backend.span_location(bb).unwrap();
+    /// let bucket = backend.buckets().nth(location.bucket).unwrap();
+    /// assert_eq!(&bucket[location.offset..][..location.len], "bb");
+    /// ```
+    pub fn span_location(&self, symbol: S) -> Option<SpanLocation> {
+        let string = self.spans.get(symbol.to_usize())?.as_str();
+        let start = string.as_ptr() as usize;
+        self.buckets()
+            .enumerate()
+            .find_map(|(bucket, contents)| {
+                let offset = start.checked_sub(contents.as_ptr() as usize)?;
+                (offset + string.len() <= contents.len()).then_some(SpanLocation {
+                    bucket,
+                    offset,
+                    len: string.len(),
+                })
+            })
+    }
+
+    /// Checks the internal invariants of the backend.
+    ///
+    /// Every span must either lie entirely within the filled part of a single
+    /// bucket or entirely outside of all buckets, in which case it is assumed to
+    /// refer to a `'static` string. Spans within a bucket must start and end on
+    /// `char` boundaries.
+    ///
+    /// Returns a description of the first violated invariant if any.
+    #[cfg(any(test, feature = "validate"))]
+    pub fn validate(&self) -> Result<(), String> {
+        use alloc::format;
+
+        let len_iter = self.iter().count();
+        if len_iter != self.spans.len() {
+            return Err(format!(
+                "iterator yielded {} strings but there are {} spans",
+                len_iter,
+                self.spans.len()
+            ));
+        }
+        for (index, span) in self.spans.iter().enumerate() {
+            let string = span.as_str();
+            if string.is_empty() {
+                continue;
+            }
+            let start = string.as_ptr() as usize;
+            let end = start + string.len();
+            for (bucket_index, bucket) in self.buckets().enumerate() {
+                if bucket.is_empty() {
+                    continue;
+                }
+                let bucket_start = bucket.as_ptr() as usize;
+                let bucket_end = bucket_start + bucket.len();
+                let starts_within = bucket_start <= start && start < bucket_end;
+                let ends_within = bucket_start < end && end <= bucket_end;
+                match (starts_within, ends_within) {
+                    (false, false) if start < bucket_start && bucket_end < end => {
+                        return Err(format!(
+                            "span {} encloses bucket {}",
+                            index, bucket_index
+                        ))
+                    }
+                    (false, false) => continue,
+                    (true, true) => {
+                        let offset = start - bucket_start;
+                        if !bucket.is_char_boundary(offset)
+                            || !bucket.is_char_boundary(offset + string.len())
+                        {
+                            return Err(format!(
+                                "span {} is not aligned to char boundaries of bucket {}",
+                                index, bucket_index
+                            ));
+                        }
+                    }
+                    _ => {
+                        return Err(format!(
+                            "span {} partially overlaps bucket {}",
+                            index, bucket_index
+                        ))
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets a callback that is invoked whenever a new bucket is allocated.
+    ///
+    /// The callback receives the requested capacity, i.e. the number of bytes
+    /// that did not fit into the previous bucket, and the capacity actually
+    /// allocated for the new bucket. Use `None` to remove the callback.
+    ///
+    /// # Note
+    ///
+    /// The callback is not cloned when the backend is cloned.
+    pub fn set_on_grow(&mut self, on_grow: Option<GrowHook>) {
+        self.on_grow = on_grow;
+    }
+
+    /// Returns the fixed capacity in bytes of newly allocated buckets if any.
+    #[inline]
+    pub fn fixed_bucket_size(&self) -> Option<usize> {
+        self.fixed_bucket_size
+    }
+
+    /// Disables the growth of the bucket capacity.
+    ///
+    /// By default every new bucket has at least twice the capacity of the
+    /// previous one. With a fixed bucket size all new buckets are allocated
+    /// with a capacity of `bucket_size` bytes instead, only strings longer
+    /// than that receive a bucket of their own length. Use `None` to restore
+    /// the default growth.
+    ///
+    /// Small fixed bucket sizes produce many buckets which is useful to
+    /// exercise multi-bucket layouts in tests. The setting is kept when the
+    /// backend is cloned.
+    ///
+    /// # Panics
+    ///
+    /// If `bucket_size` exceeds `isize::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::backend::{Backend, BucketBackend};
+    /// let mut backend = <BucketBackend>::with_fixed_bucket_size(4);
+    /// for string in ["aa", "bb", "cc", "dd"] {
+    ///     let _ = backend.intern(string);
+    /// }
+    /// assert_eq!(backend.buckets().count(), 2);
+    /// ```
+    pub fn set_fixed_bucket_size(&mut self, bucket_size: Option<usize>) {
+        if let Some(bucket_size) = bucket_size {
+            assert_bucket_capacity(bucket_size);
+        }
+        self.fixed_bucket_size = bucket_size;
+    }
+
+    /// Returns `true` if bucket growth is bounded, see [`BucketBackend::set_bounded_growth`].
+    #[inline]
+    pub fn bounded_growth(&self) -> bool {
+        self.bounded_growth
+    }
+
+    /// Limits the growth of the bucket capacity to strings that do not fit.
+    ///
+    /// By default every new bucket has at least twice the capacity of the
+    /// previous one, even if the previous head merely filled up with small
+    /// strings. With bounded growth a new bucket keeps the capacity of the
+    /// previous head and only grows if the string that did not fit is longer
+    /// than that capacity. This reduces the memory overhead of workloads that
+    /// intern many small strings at the cost of allocating more buckets.
+    ///
+    /// A [fixed bucket size][BucketBackend::set_fixed_bucket_size] takes
+    /// precedence. The setting is kept when the backend is cloned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::backend::{Backend, BucketBackend};
+    /// let mut backend = <BucketBackend>::default();
+    /// backend.set_bounded_growth(true);
+    /// for _ in 0..100 {
+    ///     let _ = backend.intern("aaaaaaaa");
+    /// }
+    /// let capacity = backend.capacity_report().bytes_allocated;
+    /// assert!(capacity < 2 * 100 * 8);
+    /// ```
+    pub fn set_bounded_growth(&mut self, bounded: bool) {
+        self.bounded_growth = bounded;
+    }
+
+    /// Makes sure that the head bucket can store `additional` more bytes.
+    ///
+    /// If the head bucket is too small a new head bucket is allocated right
+    /// away instead of during the next interning operation that does not fit.
+    /// This allows to control at which point the transition to a new bucket
+    /// happens.
+    pub fn ensure_head_capacity(&mut self, additional: usize) {
+        if self.head.capacity() - self.head.len() < additional {
+            self.new_head(additional);
+        }
+    }
+
+    /// Returns the capacity of the next head bucket that is able to store at
+    /// least `additional` bytes.
+    ///
+    /// The capacity is saturated at `isize::MAX` bytes.
+    ///
+    /// # Panics
+    ///
+    /// If `additional` exceeds `isize::MAX`.
+    fn next_head_capacity(&self, additional: usize) -> usize {
+        self.next_bucket_capacity(self.head.capacity(), additional)
+    }
+
+    /// Returns the capacity of the bucket following one of capacity `current`
+    /// that is able to store at least `additional` bytes.
+    ///
+    /// # Panics
+    ///
+    /// If `additional` exceeds `isize::MAX`.
+    fn next_bucket_capacity(&self, current: usize, additional: usize) -> usize {
+        assert_bucket_capacity(additional);
+        if let Some(bucket_size) = self.fixed_bucket_size {
+            return usize::max(bucket_size, additional);
+        }
+        if self.bounded_growth && additional <= current {
+            return current;
+        }
+        usize::max(current, additional)
+            .saturating_add(1)
+            .checked_next_power_of_two()
+            .map_or(MAX_BUCKET_CAPACITY, |cap| cap.min(MAX_BUCKET_CAPACITY))
+    }
+
+    /// Replaces the head bucket with a new one able to store `requested` bytes.
+    ///
+    /// The old head bucket is moved to the full buckets.
+    fn new_head(&mut self, requested: usize) {
+        let cap = self.next_head_capacity(requested);
+        self.replace_head(requested, cap);
+    }
+
+    /// Replaces the head bucket with a new one of capacity `cap` to store `requested` bytes.
+    ///
+    /// The old head bucket is moved to the full buckets.
+    fn replace_head(&mut self, requested: usize, cap: usize) {
+        assert_bucket_capacity(cap);
+        if let Some(on_grow) = &self.on_grow {
+            on_grow(requested, cap);
+        }
+        let new_head = FixedString::with_capacity(cap);
+        let old_head = core::mem::replace(&mut self.head, new_head);
+        // A head that never allocated holds no strings and is simply dropped.
+        if old_head.capacity() != 0 {
             self.full.push(old_head.finish());
         }
+    }
+
+    /// Interns a new string into the backend and returns a reference to it.
+    unsafe fn alloc(&mut self, string: &str) -> InternedStr {
+        self.ensure_head_capacity(string.len());
         self.head
             .push_str(string)
             .expect("encountered invalid head capacity (2)")
     }
+
+    /// Interns the formatted `args` without creating an intermediate [`String`].
+    ///
+    /// Returns a symbol for resolution into the formatted string. Does not
+    /// deduplicate, see [`StringInterner::get_or_intern_fmt`] for that.
+    ///
+    /// # Note
+    ///
+    /// The output is written directly into the head bucket. If it does not fit,
+    /// the part written so far is moved into a new, larger head bucket and
+    /// formatting continues there, so `args` is only formatted once.
+    ///
+    /// # Panics
+    ///
+    /// - If the backend already holds the maximum number of strings possible
+    ///   by the chosen symbol type.
+    /// - If a formatting trait implementation returns an error.
+    ///
+    /// [`StringInterner::get_or_intern_fmt`]: crate::StringInterner::get_or_intern_fmt
+    #[must_use = "the symbol is the only way to resolve the interned string"]
+    #[track_caller]
+    pub fn intern_fmt(&mut self, args: fmt::Arguments<'_>) -> S {
+        if let Some(string) = args.as_str() {
+            return self.intern_static(string);
+        }
+        let symbol = self.next_symbol();
+        // SAFETY: This is safe because we never hand out the returned
+        //         interned string instance to the outside and only operate
+        //         on it within this backend.
+        let interned = unsafe { self.alloc_fmt(args) };
+        self.spans.push(interned);
+        symbol
+    }
+
+    /// Interns the string assembled from `chars` without creating an intermediate [`String`].
+    ///
+    /// Returns a symbol for resolution into the assembled string. Does not
+    /// deduplicate, see [`StringInterner::get_or_intern_chars`] for that.
+    ///
+    /// # Note
+    ///
+    /// The chars are encoded directly into the head bucket. If they do not fit,
+    /// the part written so far is moved into a new, larger head bucket.
+    ///
+    /// # Panics
+    ///
+    /// If the backend already holds the maximum number of strings possible
+    /// by the chosen symbol type.
+    ///
+    /// [`StringInterner::get_or_intern_chars`]: crate::StringInterner::get_or_intern_chars
+    #[must_use = "the symbol is the only way to resolve the interned string"]
+    #[track_caller]
+    pub fn intern_chars<I>(&mut self, chars: I) -> S
+    where
+        I: IntoIterator<Item = char>,
+    {
+        let symbol = self.next_symbol();
+        // SAFETY: This is safe because we never hand out the returned
+        //         interned string instance to the outside and only operate
+        //         on it within this backend.
+        let interned = unsafe { self.alloc_chars(chars) };
+        self.spans.push(interned);
+        symbol
+    }
+
+    /// Interns the string assembled from `chars` and returns a reference to it.
+    unsafe fn alloc_chars<I>(&mut self, chars: I) -> InternedStr
+    where
+        I: IntoIterator<Item = char>,
+    {
+        use fmt::Write as _;
+        let mut writer = HeadWriter {
+            start: self.head.len(),
+            backend: self,
+        };
+        for c in chars {
+            // Writing into the head bucket never fails.
+            let _ = writer.write_char(c);
+        }
+        let start = writer.start;
+        self.head.interned_from(start)
+    }
+
+    /// Interns the formatted `args` into the backend and returns a reference to it.
+    unsafe fn alloc_fmt(&mut self, args: fmt::Arguments<'_>) -> InternedStr {
+        let mut writer = HeadWriter {
+            start: self.head.len(),
+            backend: self,
+        };
+        if fmt::write(&mut writer, args).is_err() {
+            let start = writer.start;
+            self.head.truncate(start);
+            panic!("a formatting trait implementation returned an error");
+        }
+        let start = writer.start;
+        self.head.interned_from(start)
+    }
+}
+
+/// Writes formatted output into the head bucket of a [`BucketBackend`].
+///
+/// The output written so far starts at byte `start` of the head bucket.
+struct HeadWriter<'a, 'i, S>
+where
+    S: Symbol,
+{
+    backend: &'a mut BucketBackend<'i, S>,
+    start: usize,
+}
+
+impl<'a, 'i, S> fmt::Write for HeadWriter<'a, 'i, S>
+where
+    S: Symbol,
+{
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        let backend = &mut *self.backend;
+        if backend.head.push_str(string).is_some() {
+            return Ok(());
+        }
+        let written = backend.head.len() - self.start;
+        backend.new_head(written + string.len());
+        if written != 0 {
+            // Move the output written so far from the retired head to the new head.
+            let old_head = backend.full.last_mut().expect("encountered missing bucket");
+            backend
+                .head
+                .push_str(&old_head[self.start..])
+                .expect("encountered invalid head capacity (3)");
+            old_head.truncate(self.start);
+        }
+        self.start = 0;
+        backend
+            .head
+            .push_str(string)
+            .expect("encountered invalid head capacity (3)");
+        Ok(())
+    }
 }
 
 impl<'i, S: Symbol> Clone for BucketBackend<'i, S> {
     fn clone(&self) -> Self {
+        let head = FixedString::with_capacity(self.clone_head_capacity());
+        let spans = Spans::with_capacity(self.spans.len());
+        self.clone_with(head, spans)
+    }
+}
+
+impl<'i, S> BucketBackend<'i, S>
+where
+    S: Symbol,
+{
+    /// Clones the backend like [`Clone::clone`] but returns an error instead
+    /// of aborting if an allocation fails.
+    ///
+    /// # Note
+    ///
+    /// A clone stores all strings in a single bucket which may be much larger
+    /// than any of the buckets of the original backend. Use this to clone
+    /// large backends under memory pressure.
+    ///
+    /// # Errors
+    ///
+    /// If the memory for the strings or their spans cannot be allocated.
+    pub fn try_clone(&self) -> Result<Self, TryReserveError> {
+        let head = FixedString::try_with_capacity(self.clone_head_capacity())?;
+        let mut spans = Vec::new();
+        spans.try_reserve_exact(self.spans.len())?;
+        Ok(self.clone_with(head, Spans::from(spans)))
+    }
+
+    /// Returns the capacity of the head bucket of a clone of the backend.
+    fn clone_head_capacity(&self) -> usize {
+        let len_strings = self
+            .spans
+            .iter()
+            .fold(0, |lhs, rhs| lhs + rhs.as_str().len());
+        len_strings + (self.head.capacity() - self.head.len())
+    }
+
+    /// Clones the backend into the given empty `head` and `spans`.
+    ///
+    /// The capacity of `head` must be [`BucketBackend::clone_head_capacity`].
+    fn clone_with(&self, mut head: FixedString, mut spans: Spans) -> Self {
         // For performance reasons we copy all cloned strings into a single cloned
-        // head string leaving the cloned `full` empty.
-        let new_head_cap =
-            self.head.capacity() + self.full.iter().fold(0, |lhs, rhs| lhs + rhs.len());
-        let mut head = FixedString::with_capacity(new_head_cap);
-        let mut spans = Vec::with_capacity(self.spans.len());
+        // head string leaving the cloned `full` empty. This includes `'static`
+        // strings which do not live in any of the buckets.
         for span in &self.spans {
             let string = span.as_str();
             let interned = head
@@ -184,13 +1346,41 @@ impl<'i, S: Symbol> Clone for BucketBackend<'i, S> {
             spans,
             head,
             full: Vec::new(),
+            group_heads: Vec::new(),
+            group_full: Vec::new(),
             marker: Default::default(),
+            on_grow: None,
+            fixed_bucket_size: self.fixed_bucket_size,
+            bounded_growth: self.bounded_growth,
         }
     }
 }
 
 impl<'i, S> Eq for BucketBackend<'i, S> where S: Symbol {}
 
+impl<'i, S, S2> RebindSymbol<'i, S2> for BucketBackend<'i, S>
+where
+    S: Symbol,
+    S2: Symbol,
+{
+    type Rebound = BucketBackend<'i, S2>;
+
+    #[inline]
+    fn rebind_symbol(self) -> Self::Rebound {
+        BucketBackend {
+            spans: self.spans,
+            head: self.head,
+            full: self.full,
+            group_heads: self.group_heads,
+            group_full: self.group_full,
+            marker: Default::default(),
+            on_grow: self.on_grow,
+            fixed_bucket_size: self.fixed_bucket_size,
+            bounded_growth: self.bounded_growth,
+        }
+    }
+}
+
 impl<'i, S> PartialEq for BucketBackend<'i, S>
 where
     S: Symbol,
@@ -246,4 +1436,633 @@ where
             .next()
             .map(|(id, interned)| (expect_valid_symbol(id), interned.as_str()))
     }
+
+    /// Skips `n` strings in constant time since the spans are indexed.
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter
+            .nth(n)
+            .map(|(id, interned)| (expect_valid_symbol(id), interned.as_str()))
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<'l, S> DoubleEndedIterator for Iter<'l, S>
+where
+    S: Symbol,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next_back()
+            .map(|(id, interned)| (expect_valid_symbol(id), interned.as_str()))
+    }
+}
+
+impl<'l, S> ExactSizeIterator for Iter<'l, S>
+where
+    S: Symbol,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a backend that spreads its strings over many buckets.
+    fn multi_bucket_backend<'i>() -> BucketBackend<'i, DefaultSymbol> {
+        let mut backend = BucketBackend::default();
+        for i in 0..100 {
+            let _ = backend.intern(&alloc::format!("string-{}", i));
+            let _ = backend.intern_static("static");
+        }
+        assert!(backend.full.len() > 1);
+        backend
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the maximum of isize::MAX bytes")]
+    fn with_capacity_rejects_huge_capacity() {
+        BucketBackend::<DefaultSymbol>::with_capacity(usize::MAX);
+    }
+
+    #[test]
+    fn try_intern_works() {
+        let mut backend = BucketBackend::<DefaultSymbol>::default();
+        let aa = backend.try_intern("aa").unwrap();
+        assert_eq!(backend.resolve(aa), Some("aa"));
+        assert_eq!(check_bucket_capacity(MAX_BUCKET_CAPACITY), Ok(()));
+        assert_eq!(
+            check_bucket_capacity(usize::MAX),
+            Err(ExceedsCapacityError::new(usize::MAX, MAX_BUCKET_CAPACITY))
+        );
+    }
+
+    #[test]
+    fn intern_chars_works() {
+        let mut backend = multi_bucket_backend();
+        let empty = backend.intern_chars(core::iter::empty());
+        let long = backend.intern_chars((0..1000).map(|i| if i % 2 == 0 { 'a' } else { 'ß' }));
+        let unicode = backend.intern_chars("aß€😀".chars());
+        assert_eq!(backend.resolve(empty), Some(""));
+        assert_eq!(backend.resolve(long).map(str::len), Some(1500));
+        assert_eq!(backend.resolve(unicode), Some("aß€😀"));
+        assert_eq!(backend.validate(), Ok(()));
+    }
+
+    #[test]
+    fn next_head_capacity_saturates() {
+        let backend = BucketBackend::<DefaultSymbol>::default();
+        assert_eq!(backend.next_head_capacity(5), 8);
+        assert_eq!(backend.next_head_capacity(8), 16);
+        assert_eq!(backend.next_head_capacity(MAX_BUCKET_CAPACITY), MAX_BUCKET_CAPACITY);
+        assert_eq!(backend.next_head_capacity(MAX_BUCKET_CAPACITY / 2 + 1), MAX_BUCKET_CAPACITY);
+    }
+
+    #[test]
+    fn intern_aligned_works() {
+        let mut backend = BucketBackend::<DefaultSymbol>::default();
+        let symbols = (0..100)
+            .map(|i| {
+                let _ = backend.intern("x");
+                backend.intern_aligned(&alloc::format!("aligned-{}", i), 16)
+            })
+            .collect::<Vec<_>>();
+        assert!(backend.full.len() > 1);
+        assert_eq!(backend.validate(), Ok(()));
+        for (i, symbol) in symbols.into_iter().enumerate() {
+            let string = backend.resolve(symbol).unwrap();
+            assert_eq!(string, alloc::format!("aligned-{}", i));
+            assert_eq!(string.as_ptr() as usize % 16, 0);
+        }
+    }
+
+    #[test]
+    fn fixed_bucket_size_works() {
+        let mut backend = BucketBackend::<DefaultSymbol>::with_fixed_bucket_size(4);
+        assert_eq!(backend.fixed_bucket_size(), Some(4));
+        let symbols = (0..100)
+            .map(|i| backend.intern(&alloc::format!("{:02}", i)))
+            .collect::<Vec<_>>();
+        let large = backend.intern("large string");
+        assert_eq!(backend.full.len(), 50);
+        assert!(backend.full.iter().all(|bucket| bucket.capacity() == 4));
+        assert_eq!(backend.head.capacity(), "large string".len());
+        assert_eq!(backend.validate(), Ok(()));
+        let cloned = backend.clone();
+        assert_eq!(cloned.fixed_bucket_size(), Some(4));
+        for (i, symbol) in symbols.into_iter().enumerate() {
+            let string = alloc::format!("{:02}", i);
+            assert_eq!(backend.resolve(symbol), Some(string.as_str()));
+            assert_eq!(cloned.resolve(symbol), Some(string.as_str()));
+        }
+        assert_eq!(cloned.resolve(large), Some("large string"));
+        backend.set_fixed_bucket_size(None);
+        let _ = backend.intern("x");
+        assert_eq!(backend.head.capacity(), 16);
+    }
+
+    #[test]
+    fn with_last_mut_works() {
+        let mut backend = BucketBackend::<DefaultSymbol>::default();
+        assert_eq!(backend.with_last_mut(|string| string.len()), None);
+        let aa = backend.intern("AA");
+        let bb = backend.intern("Bb");
+        assert_eq!(backend.with_last_mut(str::make_ascii_uppercase), Some(()));
+        assert_eq!(backend.resolve(aa), Some("AA"));
+        assert_eq!(backend.resolve(bb), Some("BB"));
+        let _ = backend.intern_static("static");
+        assert_eq!(backend.with_last_mut(|string| string.len()), None);
+        let cc = backend.intern("");
+        assert_eq!(backend.with_last_mut(|string| string.len()), Some(0));
+        assert_eq!(backend.resolve(cc), Some(""));
+        assert_eq!(backend.validate(), Ok(()));
+    }
+
+    #[test]
+    fn swap_remove_works() {
+        let mut backend = BucketBackend::<DefaultSymbol>::default();
+        let aa = backend.intern("aa");
+        let bb = backend.intern("bb");
+        let cc = backend.intern("cc");
+        assert_eq!(backend.swap_remove(aa), Some(String::from("aa")));
+        assert_eq!(backend.resolve(aa), Some("cc"));
+        assert_eq!(backend.resolve(bb), Some("bb"));
+        assert_eq!(backend.resolve(cc), None);
+        assert_eq!(backend.swap_remove(cc), None);
+        assert_eq!(backend.swap_remove(bb), Some(String::from("bb")));
+        assert_eq!(backend.resolve(aa), Some("cc"));
+        assert_eq!(backend.iter().len(), 1);
+        assert_eq!(backend.validate(), Ok(()));
+    }
+
+    #[test]
+    fn shrink_unused_works() {
+        let mut backend = multi_bucket_backend();
+        let empty = backend.intern("");
+        let strings = backend
+            .iter()
+            .map(|(_, string)| alloc::string::String::from(string))
+            .collect::<Vec<_>>();
+        assert!(backend.head.capacity() > backend.head.len());
+        assert!(backend.shrink_unused());
+        assert!(!backend.shrink_unused());
+        assert_eq!(backend.head.capacity(), backend.head.len());
+        assert_eq!(backend.validate(), Ok(()));
+        assert!(backend
+            .iter()
+            .map(|(_, string)| string)
+            .eq(strings.iter().map(alloc::string::String::as_str)));
+        assert_eq!(backend.resolve(empty), Some(""));
+        let aa = backend.intern("aa");
+        assert_eq!(backend.resolve(aa), Some("aa"));
+        assert_eq!(backend.validate(), Ok(()));
+    }
+
+    #[test]
+    fn span_location_works() {
+        let backend = multi_bucket_backend();
+        let buckets = backend.buckets().collect::<Vec<_>>();
+        let mut located = 0;
+        for (symbol, string) in backend.iter() {
+            match backend.span_location(symbol) {
+                Some(SpanLocation { bucket, offset, len }) => {
+                    assert_eq!(&buckets[bucket][offset..offset + len], string);
+                    located += 1;
+                }
+                None => assert_eq!(string, "static"),
+            }
+        }
+        assert_eq!(located, 100);
+        let invalid = DefaultSymbol::try_from_usize(1000).unwrap();
+        assert_eq!(backend.span_location(invalid), None);
+    }
+
+    #[test]
+    fn validate_works() {
+        let backend = multi_bucket_backend();
+        assert_eq!(backend.validate(), Ok(()));
+        let cloned = backend.clone();
+        assert_eq!(cloned.validate(), Ok(()));
+    }
+
+    #[test]
+    fn try_clone_works() {
+        let backend = multi_bucket_backend();
+        let cloned = backend.try_clone().unwrap();
+        assert_eq!(cloned, backend);
+        assert_eq!(cloned.head.capacity(), backend.clone().head.capacity());
+        assert!(cloned.full.is_empty());
+        assert_eq!(cloned.validate(), Ok(()));
+    }
+
+    /// Returns the symbols and owned copies of the strings of `backend`.
+    fn owned_strings(backend: &BucketBackend<DefaultSymbol>) -> Vec<(DefaultSymbol, String)> {
+        backend
+            .iter()
+            .map(|(symbol, string)| (symbol, String::from(string)))
+            .collect()
+    }
+
+    #[test]
+    fn clone_outlives_original() {
+        let mut backend = multi_bucket_backend();
+        let _ = backend.intern_grouped(0, "grouped");
+        let _ = backend.intern("");
+        let expected = owned_strings(&backend);
+        let cloned = backend.clone();
+        let try_cloned = backend.try_clone().unwrap();
+        drop(backend);
+        for (symbol, string) in &expected {
+            assert_eq!(cloned.resolve(*symbol), Some(string.as_str()));
+            assert_eq!(try_cloned.resolve(*symbol), Some(string.as_str()));
+        }
+        assert_eq!(owned_strings(&cloned), expected);
+        assert_eq!(cloned.validate(), Ok(()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn clone_is_sendable() {
+        let backend = multi_bucket_backend();
+        let expected = owned_strings(&backend);
+        let cloned = backend.clone();
+        let handle = std::thread::spawn(move || {
+            let strings = owned_strings(&cloned);
+            drop(cloned);
+            strings
+        });
+        drop(backend);
+        assert_eq!(handle.join().unwrap(), expected);
+    }
+
+    #[test]
+    fn validate_detects_misaligned_span() {
+        let mut backend = multi_bucket_backend();
+        let sym = backend.intern("äöü");
+        let string = backend.resolve(sym).unwrap();
+        // SAFETY: The bytes are within the bucket that outlives the span and
+        //         are never used as `str` other than for pointer comparisons
+        //         by `validate`.
+        let misaligned = unsafe {
+            core::str::from_utf8_unchecked(core::slice::from_raw_parts(string.as_ptr().add(1), 2))
+        };
+        backend.spans.push(unsafe { InternedStr::new(misaligned) });
+        assert!(backend.validate().is_err());
+        backend.spans.pop();
+        assert_eq!(backend.validate(), Ok(()));
+    }
+
+    #[test]
+    fn strings_in_bucket_works() {
+        let backend = multi_bucket_backend();
+        for (index, bucket) in backend.buckets().enumerate() {
+            let strings = backend.strings_in_bucket(index).collect::<Vec<_>>();
+            assert!(!strings.contains(&"static"));
+            assert_eq!(strings.concat(), bucket);
+        }
+        assert_eq!(backend.strings_in_bucket(backend.full.len() + 1).count(), 0);
+    }
+
+    #[test]
+    fn compact_storage_works() {
+        let mut backend = multi_bucket_backend();
+        let _ = backend.intern_grouped(1, "grouped");
+        let strings = backend.iter().map(|(_, string)| String::from(string)).collect::<Vec<_>>();
+        backend.compact_storage();
+        assert_eq!(backend.buckets().count(), 1);
+        assert_eq!(backend.buckets().next(), Some(strings.concat().as_str()));
+        let report = backend.capacity_report();
+        assert_eq!(report.bytes_used, report.bytes_allocated);
+        for (index, string) in strings.iter().enumerate() {
+            let symbol = DefaultSymbol::try_from_usize(index).unwrap();
+            assert_eq!(backend.resolve(symbol), Some(string.as_str()));
+        }
+        let symbol = backend.intern("after");
+        assert_eq!(backend.resolve(symbol), Some("after"));
+    }
+
+    #[test]
+    fn ensure_head_capacity_works() {
+        let mut backend = multi_bucket_backend();
+        let len_full = backend.full.len();
+        backend.ensure_head_capacity(1000);
+        assert_eq!(backend.full.len(), len_full + 1);
+        assert!(backend.head.capacity() >= 1000);
+        let _ = backend.intern(&"x".repeat(1000));
+        assert_eq!(backend.full.len(), len_full + 1);
+        backend.ensure_head_capacity(0);
+        assert_eq!(backend.full.len(), len_full + 1);
+        assert_eq!(backend.validate(), Ok(()));
+    }
+
+    #[test]
+    fn rollback_within_head_works() {
+        let mut backend = multi_bucket_backend();
+        let expected = backend.clone();
+        let checkpoint = backend.checkpoint();
+        assert_eq!(checkpoint.len(), 200);
+        let _ = backend.intern("a");
+        let _ = backend.intern_static("b");
+        assert_eq!(backend.full.len(), checkpoint.full_len);
+        // SAFETY: The checkpoint was created by this backend.
+        unsafe { backend.rollback(checkpoint) };
+        assert_eq!(backend.checkpoint(), checkpoint);
+        assert_eq!(backend, expected);
+        assert_eq!(backend.validate(), Ok(()));
+    }
+
+    #[test]
+    fn rollback_across_buckets_works() {
+        let mut backend = multi_bucket_backend();
+        let expected = backend.clone();
+        let checkpoint = backend.checkpoint();
+        for i in 0..100 {
+            let _ = backend.intern(&alloc::format!("other-{}", i));
+        }
+        assert!(backend.full.len() > checkpoint.full_len);
+        // SAFETY: The checkpoint was created by this backend.
+        unsafe { backend.rollback(checkpoint) };
+        assert_eq!(backend.checkpoint(), checkpoint);
+        assert_eq!(backend, expected);
+        assert_eq!(backend.validate(), Ok(()));
+        let sym = backend.intern("new");
+        assert_eq!(backend.resolve(sym), Some("new"));
+        assert_eq!(backend.validate(), Ok(()));
+    }
+
+    #[test]
+    fn from_borrowed_works() {
+        let data = String::from("aabbbcé");
+        let mut backend =
+            BucketBackend::<DefaultSymbol>::from_borrowed(&data, [0..2, 2..5, 5..8]);
+        let strings = backend.iter().map(|(_, string)| string).collect::<Vec<_>>();
+        assert_eq!(strings, ["aa", "bbb", "cé"]);
+        assert_eq!(strings[1].as_ptr(), data[2..].as_ptr());
+        assert_eq!(backend.capacity_report().bytes_allocated, 0);
+        let dd = backend.intern("dd");
+        assert_eq!(backend.resolve(dd), Some("dd"));
+        assert_eq!(backend.validate(), Ok(()));
+        let cloned = backend.clone();
+        let aa = cloned.iter().next().unwrap().1;
+        assert_ne!(aa.as_ptr(), data.as_ptr());
+        assert_eq!(cloned, backend);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_borrowed_panics_off_char_boundary() {
+        let _ = BucketBackend::<DefaultSymbol>::from_borrowed("é", core::iter::once(0..1));
+    }
+
+    #[test]
+    fn iter_nth_works() {
+        let backend = multi_bucket_backend();
+        let strings = backend.iter().collect::<Vec<_>>();
+        let mut iter = backend.iter();
+        assert_eq!(iter.nth(1), Some(strings[1]));
+        assert_eq!(iter.nth(4), Some(strings[6]));
+        assert_eq!(iter.len(), strings.len() - 7);
+        assert_eq!(iter.nth(strings.len()), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(backend.iter().nth(strings.len() - 1), strings.last().copied());
+    }
+
+    #[test]
+    fn intern_grouped_works() {
+        let mut backend = BucketBackend::<DefaultSymbol>::default();
+        let a = backend.intern_grouped(1, "aa");
+        let b = backend.intern("bb");
+        let c = backend.intern_grouped(1, "cc");
+        let d = backend.intern_grouped(0, "dd");
+        assert_eq!(backend.resolve(a), Some("aa"));
+        assert_eq!(backend.resolve(b), Some("bb"));
+        assert_eq!(backend.resolve(c), Some("cc"));
+        assert_eq!(backend.resolve(d), Some("dd"));
+        let a_ptr = backend.resolve(a).unwrap().as_ptr();
+        let c_ptr = backend.resolve(c).unwrap().as_ptr();
+        assert_eq!(a_ptr.wrapping_add(2), c_ptr);
+        // Grow the group head so that its first bucket becomes full.
+        let e = backend.intern_grouped(1, &"e".repeat(100));
+        assert_eq!(backend.group_full.len(), 1);
+        assert_eq!(backend.resolve(a), Some("aa"));
+        assert_eq!(backend.resolve(e).map(str::len), Some(100));
+        assert_eq!(backend.capacity_report().buckets, backend.buckets().count());
+        assert_eq!(backend.validate(), Ok(()));
+        assert_eq!(backend.clone(), backend);
+    }
+
+    #[test]
+    fn rollback_keeps_group_buckets() {
+        let mut backend = multi_bucket_backend();
+        let a = backend.intern_grouped(0, "aa");
+        let checkpoint = backend.checkpoint();
+        for i in 0..100 {
+            let _ = backend.intern_grouped(0, &alloc::format!("other-{}", i));
+            let _ = backend.intern(&alloc::format!("other-{}", i));
+        }
+        // SAFETY: The checkpoint was created by this backend.
+        unsafe { backend.rollback(checkpoint) };
+        assert_eq!(backend.resolve(a), Some("aa"));
+        assert_eq!(backend.validate(), Ok(()));
+        let b = backend.intern_grouped(0, "bb");
+        assert_eq!(backend.resolve(b), Some("bb"));
+    }
+
+    #[test]
+    fn bounded_growth_works() {
+        let mut backend = BucketBackend::<DefaultSymbol>::default();
+        backend.set_bounded_growth(true);
+        assert!(backend.bounded_growth());
+        for _ in 0..8 {
+            let _ = backend.intern("aaaa");
+        }
+        assert!(backend.full.iter().all(|bucket| bucket.capacity() == 8));
+        assert_eq!(backend.head.capacity(), 8);
+        // Strings longer than the current capacity still grow the buckets.
+        let _ = backend.intern(&"b".repeat(9));
+        assert_eq!(backend.head.capacity(), 16);
+        let _ = backend.intern(&"c".repeat(8));
+        assert_eq!(backend.head.capacity(), 16);
+        assert!(backend.clone().bounded_growth());
+        backend.set_bounded_growth(false);
+        let _ = backend.intern(&"d".repeat(16));
+        assert_eq!(backend.head.capacity(), 32);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn on_grow_works() {
+        use std::sync::{Arc, Mutex};
+
+        let grows = Arc::new(Mutex::new(Vec::new()));
+        let mut backend = BucketBackend::<DefaultSymbol>::default();
+        let recorded = grows.clone();
+        backend.set_on_grow(Some(Box::new(move |requested, allocated| {
+            recorded.lock().unwrap().push((requested, allocated));
+        })));
+        let _ = backend.intern("aaaa");
+        let _ = backend.intern("a");
+        let _ = backend.intern(&"b".repeat(10));
+        assert_eq!(*grows.lock().unwrap(), [(4, 8), (10, 16)]);
+        backend.set_on_grow(None);
+        let _ = backend.intern(&"c".repeat(100));
+        assert_eq!(grows.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn into_buckets_works() {
+        let backend = multi_bucket_backend();
+        let len_full = backend.full.len();
+        let expected = backend.buckets().collect::<String>();
+        let buckets = backend.into_buckets();
+        assert_eq!(buckets.len(), len_full + 1);
+        assert_eq!(buckets.concat(), expected);
+        assert!(BucketBackend::<DefaultSymbol>::default()
+            .into_buckets()
+            .is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn intern_fails_cleanly_when_symbols_are_exhausted() {
+        use crate::symbol::SymbolU16;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut backend = BucketBackend::<SymbolU16>::default();
+        for _ in 0..=SymbolU16::MAX_INDEX {
+            let _ = backend.intern("a");
+        }
+        let head_len = backend.head.len();
+        let len_full = backend.full.len();
+        assert!(catch_unwind(AssertUnwindSafe(|| backend.intern("b"))).is_err());
+        assert_eq!(backend.spans.len(), SymbolU16::MAX_INDEX + 1);
+        assert_eq!(backend.head.len(), head_len);
+        assert_eq!(backend.full.len(), len_full);
+        assert_eq!(backend.validate(), Ok(()));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "symbol out of range")]
+    fn resolve_unchecked_panics_on_foreign_symbol() {
+        let mut backend = BucketBackend::<DefaultSymbol>::default();
+        let _ = backend.intern("a");
+        let foreign = DefaultSymbol::try_from_usize(1).unwrap();
+        // SAFETY: Deliberately violated to trigger the debug assertion.
+        let _ = unsafe { backend.resolve_unchecked(foreign) };
+    }
+
+    #[test]
+    fn tiny_works() {
+        let mut backend = BucketBackend::<DefaultSymbol>::tiny();
+        #[cfg(not(feature = "smallvec-spans"))]
+        assert_eq!(backend.spans.capacity(), 4);
+        assert_eq!(backend.full.capacity(), 1);
+        let a = backend.intern("a");
+        assert_eq!(backend.resolve(a), Some("a"));
+        let default = BucketBackend::<DefaultSymbol>::default();
+        #[cfg(not(feature = "smallvec-spans"))]
+        assert_eq!(default.spans.capacity(), BucketBackend::<DefaultSymbol>::DEFAULT_SPAN_CAP);
+        assert_eq!(default.full.capacity(), BucketBackend::<DefaultSymbol>::DEFAULT_FULL_CAP);
+    }
+
+    #[test]
+    fn capacity_report_works() {
+        let backend = multi_bucket_backend();
+        let report = backend.capacity_report();
+        assert_eq!(report.buckets, backend.buckets().count());
+        assert_eq!(report.bytes_used, backend.buckets().map(str::len).sum::<usize>());
+        assert!(report.bytes_allocated >= report.bytes_used);
+        assert!(report.spans_capacity >= backend.spans.len());
+        assert_eq!(report.strings, 0);
+        let empty = BucketBackend::<DefaultSymbol>::default().capacity_report();
+        // Inline spans are reported as capacity with the `smallvec-spans` feature.
+        let empty = CapacityReport {
+            spans_capacity: 0,
+            ..empty
+        };
+        assert_eq!(empty, CapacityReport::default());
+    }
+
+    /// Writes `count` copies of `piece` in separate `write_str` calls.
+    struct Pieces(&'static str, usize);
+
+    impl fmt::Display for Pieces {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            (0..self.1).try_for_each(|_| f.write_str(self.0))
+        }
+    }
+
+    #[test]
+    fn intern_fmt_works() {
+        let mut backend = multi_bucket_backend();
+        let len_full = backend.full.len();
+        let head_free = backend.head.capacity() - backend.head.len();
+        // Overflows the head bucket half way through formatting.
+        let pieces = Pieces("abcd", head_free / 4 + 8);
+        let long = backend.intern_fmt(format_args!("<{}>", pieces));
+        assert_eq!(backend.full.len(), len_full + 1);
+        let expected = alloc::format!("<{}>", pieces);
+        assert_eq!(backend.resolve(long), Some(expected.as_str()));
+        let short = backend.intern_fmt(format_args!("{}-{}", "a", 1));
+        assert_eq!(backend.resolve(short), Some("a-1"));
+        let literal = backend.intern_fmt(format_args!("literal"));
+        assert_eq!(backend.resolve(literal), Some("literal"));
+        assert_eq!(backend.validate(), Ok(()));
+        // A fresh backend has no head bucket to move partial output out of.
+        let mut fresh = BucketBackend::<DefaultSymbol>::default();
+        let sym = fresh.intern_fmt(format_args!("{}", Pieces("ab", 3)));
+        assert_eq!(fresh.resolve(sym), Some("ababab"));
+        assert_eq!(fresh.validate(), Ok(()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn intern_fmt_discards_output_on_error() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        struct Failing;
+
+        impl fmt::Display for Failing {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("partial")?;
+                Err(fmt::Error)
+            }
+        }
+
+        let mut backend = multi_bucket_backend();
+        let checkpoint = backend.checkpoint();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            backend.intern_fmt(format_args!("{}", Failing))
+        }));
+        assert!(result.is_err());
+        assert_eq!(backend.checkpoint(), checkpoint);
+        assert_eq!(backend.validate(), Ok(()));
+    }
+
+    #[test]
+    fn intern_borrowed_works() {
+        let source = multi_bucket_backend();
+        let mut backend = BucketBackend::<DefaultSymbol>::default();
+        for (symbol, string) in source.iter() {
+            // SAFETY: `source` outlives `backend` and is not modified.
+            let borrowed = unsafe { backend.intern_borrowed(string) };
+            assert_eq!(borrowed, symbol);
+            assert_eq!(backend.resolve(borrowed).unwrap().as_ptr(), string.as_ptr());
+        }
+        assert_eq!(backend.head.capacity(), 0);
+        assert!(backend.full.is_empty());
+        let cloned = backend.clone();
+        drop(backend);
+        assert!(cloned.iter().map(|(_, s)| s).eq(source.iter().map(|(_, s)| s)));
+    }
 }
@@ -0,0 +1,134 @@
+#![cfg(feature = "rayon")]
+
+use super::BucketBackend;
+use crate::{symbol::expect_valid_symbol, Symbol};
+use rayon::{
+    iter::{
+        plumbing::{Consumer, ProducerCallback, UnindexedConsumer},
+        IndexedParallelIterator,
+        IntoParallelIterator,
+        MapWith,
+        ParallelIterator,
+    },
+    range,
+};
+
+/// Resolves the string at `index` of `backend` together with its symbol.
+fn resolve_index<'i, 'l, S>(backend: &mut &'l BucketBackend<'i, S>, index: usize) -> (S, &'l str)
+where
+    S: Symbol,
+{
+    let backend: &'l BucketBackend<'i, S> = backend;
+    (expect_valid_symbol(index), backend.spans[index].as_str())
+}
+
+/// Parallel iterator over the symbols and strings of a [`BucketBackend`].
+///
+/// Splits the range of symbols among the threads of the [`rayon`] thread pool.
+pub struct ParIter<'i, 'l, S>
+where
+    S: Symbol,
+{
+    #[allow(clippy::type_complexity)]
+    inner: MapWith<
+        range::Iter<usize>,
+        &'l BucketBackend<'i, S>,
+        fn(&mut &'l BucketBackend<'i, S>, usize) -> (S, &'l str),
+    >,
+}
+
+impl<'i, 'l, S> ParIter<'i, 'l, S>
+where
+    S: Symbol + Send,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub(super) fn new(backend: &'l BucketBackend<'i, S>) -> Self {
+        Self {
+            inner: (0..backend.spans.len())
+                .into_par_iter()
+                .map_with(backend, resolve_index),
+        }
+    }
+}
+
+impl<'i, 'l, S> ParallelIterator for ParIter<'i, 'l, S>
+where
+    S: Symbol + Send,
+{
+    type Item = (S, &'l str);
+
+    #[inline]
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        self.inner.drive_unindexed(consumer)
+    }
+
+    #[inline]
+    fn opt_len(&self) -> Option<usize> {
+        self.inner.opt_len()
+    }
+}
+
+impl<'i, 'l, S> IndexedParallelIterator for ParIter<'i, 'l, S>
+where
+    S: Symbol + Send,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline]
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: Consumer<Self::Item>,
+    {
+        self.inner.drive(consumer)
+    }
+
+    #[inline]
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        self.inner.with_producer(callback)
+    }
+}
+
+impl<'i, 'l, S> IntoParallelIterator for &'l BucketBackend<'i, S>
+where
+    S: Symbol + Send,
+{
+    type Item = (S, &'l str);
+    type Iter = ParIter<'i, 'l, S>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_par_iter(self) -> Self::Iter {
+        ParIter::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{backend::Backend, DefaultSymbol};
+    use alloc::{format, vec::Vec};
+    use rayon::iter::IntoParallelRefIterator;
+
+    #[test]
+    fn par_iter_works() {
+        let mut backend = BucketBackend::<DefaultSymbol>::default();
+        for i in 0..1000 {
+            let _ = backend.intern(&format!("string-{}", i));
+        }
+        let _ = backend.intern_static("static");
+        let parallel = backend.par_iter().collect::<Vec<_>>();
+        let sequential = backend.iter().collect::<Vec<_>>();
+        assert_eq!(parallel.len(), 1001);
+        assert_eq!(parallel, sequential);
+        let total = backend.par_iter().map(|(_, string)| string.len()).sum::<usize>();
+        assert_eq!(total, sequential.iter().map(|(_, string)| string.len()).sum());
+    }
+}
@@ -1,7 +1,7 @@
 #![cfg(feature = "backends")]
 
-use super::{Backend, PhantomBackend};
-use crate::{symbol::expect_valid_symbol, DefaultSymbol, Symbol};
+use super::{Backend, PhantomBackend, RebindSymbol};
+use crate::{symbol::expect_valid_symbol, CapacityReport, DefaultSymbol, Symbol};
 use alloc::vec::Vec;
 use core::{mem, str};
 
@@ -38,6 +38,23 @@ where
 
 impl<'i, S> Eq for BufferBackend<'i, S> where S: Symbol {}
 
+impl<'i, S, S2> RebindSymbol<'i, S2> for BufferBackend<'i, S>
+where
+    S: Symbol,
+    S2: Symbol,
+{
+    type Rebound = BufferBackend<'i, S2>;
+
+    #[inline]
+    fn rebind_symbol(self) -> Self::Rebound {
+        BufferBackend {
+            len_strings: self.len_strings,
+            buffer: self.buffer,
+            marker: Default::default(),
+        }
+    }
+}
+
 impl<'i, S: Symbol> Clone for BufferBackend<'i, S> {
     fn clone(&self) -> Self {
         Self {
@@ -64,9 +81,16 @@ where
     S: Symbol,
 {
     /// Returns the next available symbol.
+    ///
+    /// # Panics
+    ///
+    /// If the buffer already holds more than `S::MAX_INDEX` bytes.
     #[inline]
+    #[track_caller]
     fn next_symbol(&self) -> S {
-        expect_valid_symbol(self.buffer.len())
+        let index = self.buffer.len();
+        assert!(index <= S::MAX_INDEX, "symbol space exhausted");
+        expect_valid_symbol(index)
     }
 
     /// Resolves the string for the given symbol if any.
@@ -138,6 +162,7 @@ impl<'i, S> Backend<'i> for BufferBackend<'i, S>
 where
     S: Symbol,
 {
+    const SYMBOLS_MONOTONIC: bool = true;
     type Access<'l> = &'l str
     where
          Self: 'l;
@@ -162,6 +187,16 @@ where
         }
     }
 
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn with_capacity_and_bytes(cap: usize, bytes: usize) -> Self {
+        // Each string is prefixed by at least one byte encoding its length.
+        Self {
+            len_strings: 0,
+            buffer: Vec::with_capacity(bytes + cap),
+            marker: Default::default(),
+        }
+    }
+
     #[inline]
     fn intern(&mut self, string: &str) -> Self::Symbol {
         self.push_string(string)
@@ -175,10 +210,33 @@ where
         }
     }
 
+    fn reserve(&mut self, additional: usize) {
+        /// We encode the `usize` string length into the buffer as well.
+        const LEN_USIZE: usize = mem::size_of::<usize>();
+        /// According to google the approx. word length is 5.
+        const DEFAULT_STR_LEN: usize = 5;
+        let bytes_per_string = DEFAULT_STR_LEN + LEN_USIZE;
+        self.buffer.reserve(additional * bytes_per_string);
+    }
+
+    fn reserve_exact(&mut self, additional: usize, bytes: usize) {
+        // Each string is prefixed by at least one byte encoding its length.
+        self.buffer.reserve_exact(bytes + additional);
+    }
+
     fn shrink_to_fit(&mut self) {
         self.buffer.shrink_to_fit();
     }
 
+    #[inline]
+    fn capacity_report(&self) -> CapacityReport {
+        CapacityReport {
+            bytes_used: self.buffer.len(),
+            bytes_allocated: self.buffer.capacity(),
+            ..CapacityReport::default()
+        }
+    }
+
     #[inline]
     unsafe fn resolve_unchecked(&self, symbol: Self::Symbol) -> &str {
         // SAFETY: The function is marked unsafe so that the caller guarantees
@@ -368,9 +426,20 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::{decode_var_usize, encode_var_usize};
+    use super::{decode_var_usize, encode_var_usize, BufferBackend};
+    use crate::{backend::Backend, symbol::SymbolU16};
     use alloc::vec::Vec;
 
+    #[test]
+    #[should_panic(expected = "symbol space exhausted")]
+    fn intern_panics_past_symbol_offsets() {
+        let mut backend = BufferBackend::<SymbolU16>::default();
+        let x = backend.intern("x");
+        let _ = backend.intern(&"y".repeat(70_000));
+        assert_eq!(backend.resolve(x), Some("x"));
+        let _ = backend.intern("z");
+    }
+
     #[test]
     fn encode_var_usize_1_byte_works() {
         let mut buffer = Vec::new();
@@ -9,8 +9,15 @@ mod buffer;
 mod string;
 
 #[cfg(feature = "backends")]
-pub use self::{bucket::BucketBackend, buffer::BufferBackend, string::StringBackend};
-use crate::Symbol;
+pub use self::{
+    bucket::{BucketBackend, Checkpoint, GroupId, GrowHook, SpanLocation},
+    buffer::BufferBackend,
+    string::StringBackend,
+};
+#[cfg(all(feature = "backends", feature = "rayon"))]
+pub use self::bucket::ParIter;
+use crate::{CapacityReport, Symbol};
+use alloc::borrow::Cow;
 
 /// The default backend recommended for general use.
 #[cfg(feature = "backends")]
@@ -24,6 +31,20 @@ type PhantomBackend<'i, B: Backend<'i>> = std::marker::PhantomData<
     (core::cell::Cell<&'i ()>, fn() -> <B as Backend<'i>>::Symbol)
 >;
 
+/// Describes whether the strings resolved by a [`Backend`] stay in place.
+///
+/// See [`Backend::ACCESS_KIND`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AccessKind {
+    /// Interned strings are never moved by interning further strings.
+    ///
+    /// Their contents stay at the same address for as long as the backend
+    /// is neither dropped nor otherwise mutated to free them.
+    Stable,
+    /// Interned strings may be moved in memory when further strings are interned.
+    Volatile,
+}
+
 /// Types implementing this trait may act as backends for the string interner.
 ///
 /// The job of a backend is to actually store, manage and organize the interned
@@ -46,6 +67,33 @@ pub trait Backend<'i>: Default {
         Self: 'l,
         'i: 'l;
 
+    /// Whether the strings resolved by this backend stay in place when further
+    /// strings are interned.
+    ///
+    /// Generic code can branch on this at compile time, for example to copy
+    /// resolved strings only if they would be invalidated by interning, see
+    /// [`resolve_owned_if_volatile`](`Backend::resolve_owned_if_volatile`).
+    ///
+    /// # Note
+    ///
+    /// Defaults to [`AccessKind::Volatile`] which is correct for every backend.
+    const ACCESS_KIND: AccessKind = AccessKind::Volatile;
+
+    /// Whether the symbols of this backend increase with every interned string.
+    ///
+    /// If `true` the backend guarantees that for symbols `a` and `b` returned
+    /// by [`intern`](`Backend::intern`) and
+    /// [`intern_static`](`Backend::intern_static`), `a < b` as `usize` if and
+    /// only if `a` has been interned before `b`, and that
+    /// [`iter`](`Backend::iter`) yields the strings in ascending symbol order.
+    /// This only holds for as long as no strings are removed, for example by
+    /// [`BucketBackend::swap_remove`].
+    ///
+    /// # Note
+    ///
+    /// Defaults to `false` which is correct for every backend.
+    const SYMBOLS_MONOTONIC: bool = false;
+
     /// The iterator over the symbols and their strings.
     type Iter<'l>: Iterator<Item = (Self::Symbol, Self::Access<'l>)>
     where
@@ -55,14 +103,38 @@ pub trait Backend<'i>: Default {
     /// Creates a new backend for the given capacity.
     ///
     /// The capacity denotes how many strings are expected to be interned.
+    #[track_caller]
     fn with_capacity(cap: usize) -> Self;
 
+    /// Creates a new backend for the given capacity in strings and bytes.
+    ///
+    /// The capacity `cap` denotes how many strings are expected to be interned
+    /// and `bytes` how many bytes those strings occupy in total.
+    ///
+    /// # Note
+    ///
+    /// The default implementation ignores `bytes` and forwards to
+    /// [`with_capacity`](`Backend::with_capacity`).
+    #[track_caller]
+    #[inline]
+    fn with_capacity_and_bytes(cap: usize, bytes: usize) -> Self {
+        let _ = bytes;
+        Self::with_capacity(cap)
+    }
+
     /// Interns the given string and returns its interned ref and symbol.
     ///
     /// # Note
     ///
     /// The backend must make sure that the returned symbol maps back to the
     /// original string in its [`resolve`](`Backend::resolve`) method.
+    ///
+    /// Backends do not deduplicate: interning the same string twice stores it
+    /// twice and returns two distinct symbols. Use
+    /// [`StringInterner::get_or_intern`](crate::StringInterner::get_or_intern)
+    /// for deduplicated interning.
+    #[must_use = "the symbol is the only way to resolve the interned string"]
+    #[track_caller]
     fn intern(&mut self, string: &str) -> Self::Symbol;
 
     /// Interns the given static string and returns its interned ref and symbol.
@@ -71,7 +143,14 @@ pub trait Backend<'i>: Default {
     ///
     /// The backend must make sure that the returned symbol maps back to the
     /// original string in its [`resolve`](`Backend::resolve`) method.
+    ///
+    /// Like [`Backend::intern`] this does not deduplicate, not even for the
+    /// identical `&'static str`. Use
+    /// [`StringInterner::get_or_intern_static`](crate::StringInterner::get_or_intern_static)
+    /// for deduplicated interning.
     #[inline]
+    #[must_use = "the symbol is the only way to resolve the interned string"]
+    #[track_caller]
     fn intern_static(&mut self, string: &'static str) -> Self::Symbol {
         // The default implementation simply forwards to the normal [`intern`]
         // implementation. Backends that can optimize for this use case should
@@ -79,12 +158,121 @@ pub trait Backend<'i>: Default {
         self.intern(string)
     }
 
+    /// Returns the symbol of the given string if the backend can look it up cheaply.
+    ///
+    /// Backends that maintain their own reverse mapping from strings to symbols
+    /// may implement this so that [`StringInterner::get`](crate::StringInterner::get)
+    /// does not have to consult its hash map.
+    ///
+    /// # Note
+    ///
+    /// Returning `None` means that the string interner should ask its hash map
+    /// instead. The default implementation always returns `None`.
+    #[inline]
+    fn get(&self, string: &str) -> Option<Self::Symbol> {
+        let _ = string;
+        None
+    }
+
+    /// Reserves capacity for at least `additional` more strings to be interned.
+    ///
+    /// # Note
+    ///
+    /// Backends cannot know the lengths of the strings that are going to be
+    /// interned so they may only reserve space for an estimated string length.
+    /// The default implementation does nothing.
+    #[inline]
+    fn reserve(&mut self, additional: usize) {
+        let _ = additional;
+    }
+
+    /// Reserves capacity for exactly `additional` more strings of `bytes` bytes in total.
+    ///
+    /// Unlike [`reserve`](`Backend::reserve`) this does not deliberately
+    /// over-allocate, which is useful if the number and size of the strings
+    /// to be interned are known up front.
+    ///
+    /// # Note
+    ///
+    /// The default implementation ignores `bytes` and forwards to
+    /// [`reserve`](`Backend::reserve`).
+    #[inline]
+    fn reserve_exact(&mut self, additional: usize, bytes: usize) {
+        let _ = bytes;
+        self.reserve(additional);
+    }
+
     /// Shrink backend capacity to fit interned symbols exactly.
     fn shrink_to_fit(&mut self);
 
+    /// Returns a report about the memory used and allocated by the backend.
+    ///
+    /// # Note
+    ///
+    /// The [`strings`][CapacityReport::strings] and
+    /// [`map_capacity`][CapacityReport::map_capacity] fields are filled in by the
+    /// string interner and ignored. The default implementation reports nothing.
+    #[inline]
+    fn capacity_report(&self) -> CapacityReport {
+        CapacityReport::default()
+    }
+
     /// Resolves the given symbol to its original string contents.
     fn resolve(&self, symbol: Self::Symbol) -> Option<Self::Access<'_>>;
 
+    /// Resolves the given symbol to the bytes of its original string contents.
+    ///
+    /// # Note
+    ///
+    /// Only available for backends that resolve to borrowed strings. The default
+    /// implementation converts the result of [`resolve`](`Backend::resolve`).
+    #[inline]
+    fn resolve_bytes<'l>(&'l self, symbol: Self::Symbol) -> Option<&'l [u8]>
+    where
+        'i: 'l,
+        Self::Access<'l>: Into<&'l str>,
+    {
+        self.resolve(symbol).map(|string| string.into().as_bytes())
+    }
+
+    /// Resolves the given symbol to its original string contents as a [`Cow`].
+    ///
+    /// Allows generic code to handle backends that resolve to borrowed strings
+    /// and backends that resolve to owned strings uniformly.
+    ///
+    /// # Note
+    ///
+    /// The default implementation converts the result of
+    /// [`resolve`](`Backend::resolve`), which is [`Cow::Borrowed`] for the
+    /// backends of this crate.
+    #[inline]
+    fn resolve_cow<'l>(&'l self, symbol: Self::Symbol) -> Option<Cow<'l, str>>
+    where
+        'i: 'l,
+        Self::Access<'l>: Into<Cow<'l, str>>,
+    {
+        self.resolve(symbol).map(Into::into)
+    }
+
+    /// Resolves the given symbol to its original string contents, copying them
+    /// if the backend may move them.
+    ///
+    /// Returns [`Cow::Borrowed`] for [`AccessKind::Stable`] backends and
+    /// [`Cow::Owned`] for [`AccessKind::Volatile`] backends, see
+    /// [`ACCESS_KIND`](`Backend::ACCESS_KIND`).
+    #[inline]
+    fn resolve_owned_if_volatile<'l>(&'l self, symbol: Self::Symbol) -> Option<Cow<'l, str>>
+    where
+        'i: 'l,
+        Self::Access<'l>: Into<Cow<'l, str>>,
+    {
+        let string = self.resolve(symbol)?.into();
+        Some(match Self::ACCESS_KIND {
+            AccessKind::Stable => string,
+            AccessKind::Volatile => Cow::Owned(string.into_owned()),
+        })
+    }
+
     /// Resolves the given symbol to its original string contents.
     ///
     /// # Safety
@@ -99,3 +287,25 @@ pub trait Backend<'i>: Default {
     /// Creates an iterator that yields all interned strings and their symbols.
     fn iter(&self) -> Self::Iter<'_>;
 }
+
+/// Backends that can change their symbol type without copying their strings.
+///
+/// See [`StringInterner::with_symbol_type`](crate::StringInterner::with_symbol_type).
+pub trait RebindSymbol<'i, S2>: Backend<'i>
+where
+    S2: Symbol,
+{
+    /// The same backend using `S2` as its symbol type.
+    type Rebound: Backend<'i, Symbol = S2>;
+
+    /// Changes the symbol type of the backend to `S2`.
+    ///
+    /// The symbols of the returned backend have the same `usize`
+    /// representation as the symbols of `self`.
+    ///
+    /// # Note
+    ///
+    /// The caller has to make sure that `S2` can represent the symbols of all
+    /// strings interned so far. Otherwise iterating the returned backend panics.
+    fn rebind_symbol(self) -> Self::Rebound;
+}
@@ -1,7 +1,7 @@
 #![cfg(feature = "backends")]
 
-use super::{Backend, PhantomBackend};
-use crate::{symbol::expect_valid_symbol, DefaultSymbol, Symbol};
+use super::{Backend, PhantomBackend, RebindSymbol};
+use crate::{symbol::expect_valid_symbol, CapacityReport, DefaultSymbol, Symbol};
 use alloc::{string::String, vec::Vec};
 use core::{iter::Enumerate, slice};
 
@@ -59,6 +59,23 @@ where
 
 impl<'i, S> Eq for StringBackend<'i, S> where S: Symbol {}
 
+impl<'i, S, S2> RebindSymbol<'i, S2> for StringBackend<'i, S>
+where
+    S: Symbol,
+    S2: Symbol,
+{
+    type Rebound = StringBackend<'i, S2>;
+
+    #[inline]
+    fn rebind_symbol(self) -> Self::Rebound {
+        StringBackend {
+            ends: self.ends,
+            buffer: self.buffer,
+            marker: Default::default(),
+        }
+    }
+}
+
 impl<'i, S: Symbol> Clone for StringBackend<'i, S> {
     fn clone(&self) -> Self {
         Self {
@@ -85,8 +102,15 @@ where
     S: Symbol,
 {
     /// Returns the next available symbol.
+    ///
+    /// # Panics
+    ///
+    /// If the backend already holds `S::MAX_INDEX + 1` strings.
+    #[track_caller]
     fn next_symbol(&self) -> S {
-        expect_valid_symbol(self.ends.len())
+        let index = self.ends.len();
+        assert!(index <= S::MAX_INDEX, "symbol space exhausted");
+        expect_valid_symbol(index)
     }
 
     /// Returns the string associated to the span.
@@ -112,6 +136,7 @@ where
     /// Returns the span for the given symbol if any.
     unsafe fn symbol_to_span_unchecked(&self, symbol: S) -> Span {
         let index = symbol.to_usize();
+        debug_assert!(index < self.ends.len(), "symbol out of range");
         // SAFETY: The function is marked unsafe so that the caller guarantees
         //         that required invariants are checked.
         let to = unsafe { *self.ends.get_unchecked(index) };
@@ -125,9 +150,9 @@ where
     ///
     /// If the backend ran out of symbols.
     fn push_string(&mut self, string: &str) -> S {
+        let symbol = self.next_symbol();
         self.buffer.push_str(string);
         let to = self.buffer.len();
-        let symbol = self.next_symbol();
         self.ends.push(to);
         symbol
     }
@@ -137,6 +162,7 @@ impl<'i, S> Backend<'i> for StringBackend<'i, S>
 where
     S: Symbol,
 {
+    const SYMBOLS_MONOTONIC: bool = true;
     type Access<'l> = &'l str where Self: 'l;
 
     type Symbol = S;
@@ -156,6 +182,15 @@ where
         }
     }
 
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn with_capacity_and_bytes(cap: usize, bytes: usize) -> Self {
+        Self {
+            ends: Vec::with_capacity(cap),
+            buffer: String::with_capacity(bytes),
+            marker: Default::default(),
+        }
+    }
+
     #[inline]
     fn intern(&mut self, string: &str) -> Self::Symbol {
         self.push_string(string)
@@ -167,11 +202,33 @@ where
             .map(|span| self.span_to_str(span))
     }
 
+    fn reserve(&mut self, additional: usize) {
+        // According to google the approx. word length is 5.
+        let default_word_len = 5;
+        self.ends.reserve(additional);
+        self.buffer.reserve(additional * default_word_len);
+    }
+
+    fn reserve_exact(&mut self, additional: usize, bytes: usize) {
+        self.ends.reserve_exact(additional);
+        self.buffer.reserve_exact(bytes);
+    }
+
     fn shrink_to_fit(&mut self) {
         self.ends.shrink_to_fit();
         self.buffer.shrink_to_fit();
     }
 
+    #[inline]
+    fn capacity_report(&self) -> CapacityReport {
+        CapacityReport {
+            bytes_used: self.buffer.len(),
+            bytes_allocated: self.buffer.capacity(),
+            spans_capacity: self.ends.capacity(),
+            ..CapacityReport::default()
+        }
+    }
+
     #[inline]
     unsafe fn resolve_unchecked(&self, symbol: Self::Symbol) -> &str {
         // SAFETY: The function is marked unsafe so that the caller guarantees
@@ -236,4 +293,40 @@ where
             )
         })
     }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<'i, 'l, S> DoubleEndedIterator for Iter<'i, 'l, S>
+where
+    S: Symbol,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.ends.next_back().map(|(id, &to)| {
+            let from = self
+                .backend
+                .ends
+                .get(id.wrapping_sub(1))
+                .copied()
+                .unwrap_or(0);
+            (
+                expect_valid_symbol(id),
+                self.backend.span_to_str(Span { from, to }),
+            )
+        })
+    }
+}
+
+impl<'i, 'l, S> ExactSizeIterator for Iter<'i, 'l, S>
+where
+    S: Symbol,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.ends.len()
+    }
 }
@@ -0,0 +1,208 @@
+use crate::{backend::Backend, StringInterner, Symbol};
+use core::{fmt, fmt::Debug, hash::BuildHasher, marker::PhantomData};
+use hashbrown::DefaultHashBuilder;
+
+/// Hasher used by [`StringInternerBuilder::deterministic`].
+///
+/// Produces the same hash values across program runs.
+#[cfg(feature = "std")]
+pub type DeterministicHashBuilder =
+    core::hash::BuildHasherDefault<std::collections::hash_map::DefaultHasher>;
+
+/// Builder to configure the backend, hasher and capacities of a [`StringInterner`].
+///
+/// Without any options set [`build`][StringInternerBuilder::build] returns the
+/// same interner as [`StringInterner::new`]. The backend defaults to the
+/// [`DefaultBackend`](crate::DefaultBackend).
+///
+/// # Example
+///
+/// ```
+/// # use string_interner::{backend::BucketBackend, StringInternerBuilder};
+/// let mut interner = StringInternerBuilder::new()
+///     .backend::<BucketBackend>()
+///     .string_count(100)
+///     .backend_capacity(1024)
+///     .build();
+/// let sym = interner.get_or_intern("Banana");
+/// assert_eq!(interner.resolve(sym), Some("Banana"));
+/// ```
+pub struct StringInternerBuilder<'i, B, H = DefaultHashBuilder>
+where
+    B: Backend<'i>,
+{
+    string_count: Option<usize>,
+    backend_capacity: Option<usize>,
+    hasher: H,
+    marker: PhantomData<fn() -> StringInterner<'i, B, H>>,
+}
+
+impl<'i, B, H> Debug for StringInternerBuilder<'i, B, H>
+where
+    B: Backend<'i>,
+    H: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StringInternerBuilder")
+            .field("string_count", &self.string_count)
+            .field("backend_capacity", &self.backend_capacity)
+            .field("hasher", &self.hasher)
+            .finish()
+    }
+}
+
+#[cfg(feature = "backends")]
+impl<'i> StringInternerBuilder<'i, crate::DefaultBackend<'i>> {
+    /// Creates a new builder for an interner using the default backend and hasher.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<'i, B, H> Default for StringInternerBuilder<'i, B, H>
+where
+    B: Backend<'i>,
+    H: Default,
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self {
+            string_count: None,
+            backend_capacity: None,
+            hasher: H::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'i, B, H> StringInternerBuilder<'i, B, H>
+where
+    B: Backend<'i>,
+    <B as Backend<'i>>::Symbol: Symbol,
+    H: BuildHasher,
+{
+    /// Uses the backend `B2` for the built interner.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn backend<B2>(self) -> StringInternerBuilder<'i, B2, H>
+    where
+        B2: Backend<'i>,
+    {
+        StringInternerBuilder {
+            string_count: self.string_count,
+            backend_capacity: self.backend_capacity,
+            hasher: self.hasher,
+            marker: PhantomData,
+        }
+    }
+
+    /// Sets the number of bytes the backend should allocate up front for string contents.
+    ///
+    /// See [`Backend::with_capacity_and_bytes`].
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn backend_capacity(mut self, bytes: usize) -> Self {
+        self.backend_capacity = Some(bytes);
+        self
+    }
+
+    /// Sets the number of strings the interner should allocate up front.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn string_count(mut self, count: usize) -> Self {
+        self.string_count = Some(count);
+        self
+    }
+
+    /// Uses the given hasher for the built interner.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn hasher<H2>(self, hash_builder: H2) -> StringInternerBuilder<'i, B, H2>
+    where
+        H2: BuildHasher,
+    {
+        StringInternerBuilder {
+            string_count: self.string_count,
+            backend_capacity: self.backend_capacity,
+            hasher: hash_builder,
+            marker: PhantomData,
+        }
+    }
+
+    /// Uses the [`DeterministicHashBuilder`] for the built interner.
+    ///
+    /// Unlike the default hasher, which is randomly seeded, this hasher yields the
+    /// same hash values across program runs which makes the layout of the internal
+    /// hash map reproducible.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn deterministic(self) -> StringInternerBuilder<'i, B, DeterministicHashBuilder> {
+        self.hasher(DeterministicHashBuilder::default())
+    }
+
+    /// Builds the configured [`StringInterner`].
+    pub fn build(self) -> StringInterner<'i, B, H> {
+        let backend = match (self.string_count, self.backend_capacity) {
+            (count, Some(bytes)) => B::with_capacity_and_bytes(count.unwrap_or(0), bytes),
+            (Some(count), None) => B::with_capacity(count),
+            (None, None) => B::default(),
+        };
+        StringInterner::from_parts(backend, self.hasher, self.string_count.unwrap_or(0))
+    }
+}
+
+#[cfg(all(test, feature = "backends"))]
+mod tests {
+    use super::*;
+    use crate::backend::{BucketBackend, BufferBackend, StringBackend};
+
+    fn build_and_intern<'i, B>(builder: StringInternerBuilder<'i, B>)
+    where
+        B: Backend<'i>,
+        <B as Backend<'i>>::Symbol: Symbol,
+    {
+        let mut interner = builder.build();
+        assert!(interner.is_empty());
+        let aa = interner.get_or_intern("aa");
+        let bb = interner.get_or_intern("bb");
+        assert!(interner.get_or_intern("aa") == aa);
+        assert_eq!(interner.resolve(aa).unwrap().as_ref(), "aa");
+        assert_eq!(interner.resolve(bb).unwrap().as_ref(), "bb");
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn build_works() {
+        build_and_intern(StringInternerBuilder::new());
+        build_and_intern(StringInternerBuilder::new().string_count(10));
+        build_and_intern(StringInternerBuilder::new().backend_capacity(64));
+        build_and_intern(
+            StringInternerBuilder::new()
+                .backend::<BucketBackend>()
+                .string_count(10)
+                .backend_capacity(64),
+        );
+        build_and_intern(
+            StringInternerBuilder::new()
+                .backend::<BufferBackend>()
+                .backend_capacity(64),
+        );
+        build_and_intern(
+            StringInternerBuilder::new()
+                .backend::<StringBackend>()
+                .string_count(10),
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn deterministic_works() {
+        let build = || {
+            let mut interner = StringInternerBuilder::new().deterministic().build();
+            interner.get_or_intern("aa");
+            interner.get_or_intern("bb");
+            interner
+        };
+        let lhs = build();
+        let rhs = build();
+        assert_eq!(lhs, rhs);
+        assert_eq!(format!("{:?}", lhs), format!("{:?}", rhs));
+    }
+}
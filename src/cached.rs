@@ -0,0 +1,250 @@
+#![cfg(feature = "backends")]
+
+use crate::{backend::BucketBackend, DefaultSymbol, StringInterner, Symbol};
+use alloc::boxed::Box;
+use core::{
+    cell::Cell,
+    fmt,
+    fmt::{Debug, Formatter},
+    hash::BuildHasher,
+    iter,
+    ptr::NonNull,
+};
+use hashbrown::DefaultHashBuilder;
+
+/// A cache slot storing a symbol together with its resolved string.
+type Slot<S> = Cell<Option<(S, NonNull<str>)>>;
+
+/// A [`StringInterner`] using the [`BucketBackend`] with a cache for resolved symbols.
+///
+/// The cache is direct-mapped: every symbol maps to exactly one slot of the cache
+/// which holds the most recently resolved symbol for that slot. This speeds up
+/// programs that repeatedly resolve the same few symbols.
+///
+/// # Note
+///
+/// This is a read-through cache only. Interning strings bypasses the cache and
+/// only [`CachedInterner::resolve`] fills it. Caching the resolved strings is
+/// sound because the [`BucketBackend`] never moves or frees interned strings
+/// while it is alive.
+pub struct CachedInterner<'i, S = DefaultSymbol, H = DefaultHashBuilder>
+where
+    S: Symbol,
+{
+    interner: StringInterner<'i, BucketBackend<'i, S>, H>,
+    cache: Box<[Slot<S>]>,
+}
+
+/// # Safety
+///
+/// The cached interner requires a manual [`Send`] impl because its cache stores
+/// pointers into the buckets of its own backend. Those buckets are not moved
+/// when the cached interner is sent to another thread.
+unsafe impl<'i, S, H> Send for CachedInterner<'i, S, H>
+where
+    S: Symbol + Send,
+    H: Send,
+{
+}
+
+impl<'i, S, H> Debug for CachedInterner<'i, S, H>
+where
+    S: Symbol + Debug,
+    H: BuildHasher,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachedInterner")
+            .field("interner", &self.interner)
+            .field("cache_size", &self.cache_size())
+            .finish()
+    }
+}
+
+impl<'i, S, H> CachedInterner<'i, S, H>
+where
+    S: Symbol,
+    H: BuildHasher,
+{
+    /// The number of cache slots used by [`CachedInterner::new`].
+    pub const DEFAULT_CACHE_SIZE: usize = 64;
+
+    /// Creates a new cached interner wrapping `interner` with the default cache size.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new(interner: StringInterner<'i, BucketBackend<'i, S>, H>) -> Self {
+        Self::with_cache_size(interner, Self::DEFAULT_CACHE_SIZE)
+    }
+
+    /// Creates a new cached interner wrapping `interner` with `cache_size` cache slots.
+    ///
+    /// A `cache_size` of zero disables caching.
+    pub fn with_cache_size(
+        interner: StringInterner<'i, BucketBackend<'i, S>, H>,
+        cache_size: usize,
+    ) -> Self {
+        Self {
+            interner,
+            cache: iter::repeat_with(|| Cell::new(None))
+                .take(cache_size)
+                .collect(),
+        }
+    }
+
+    /// Returns the number of cache slots.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn cache_size(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Returns a shared reference to the wrapped interner.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn interner(&self) -> &StringInterner<'i, BucketBackend<'i, S>, H> {
+        &self.interner
+    }
+
+    /// Consumes the cached interner and returns the wrapped interner.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn into_inner(self) -> StringInterner<'i, BucketBackend<'i, S>, H> {
+        self.interner
+    }
+
+    /// Returns the number of strings interned by the interner.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.interner.len()
+    }
+
+    /// Returns `true` if the interner has no interned strings.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.interner.is_empty()
+    }
+
+    /// Returns the symbol for the given string if any.
+    #[inline]
+    pub fn get<T>(&self, string: T) -> Option<S>
+    where
+        T: AsRef<str>,
+    {
+        self.interner.get(string)
+    }
+
+    /// Interns the given string.
+    ///
+    /// Returns a symbol for resolution into the original string.
+    ///
+    /// # Panics
+    ///
+    /// See [`StringInterner::get_or_intern`].
+    #[inline]
+    pub fn get_or_intern<T>(&mut self, string: T) -> S
+    where
+        T: AsRef<str>,
+    {
+        self.interner.get_or_intern(string)
+    }
+
+    /// Interns the given `'static` string.
+    ///
+    /// Returns a symbol for resolution into the original string.
+    ///
+    /// # Panics
+    ///
+    /// See [`StringInterner::get_or_intern_static`].
+    #[inline]
+    pub fn get_or_intern_static(&mut self, string: &'static str) -> S {
+        self.interner.get_or_intern_static(string)
+    }
+
+    /// Returns the string for the given `symbol` if any.
+    ///
+    /// Consults the cache first and fills it on a miss.
+    #[inline]
+    pub fn resolve(&self, symbol: S) -> Option<&str> {
+        let Some(slot) = self.slot(symbol) else {
+            return self.interner.resolve(symbol);
+        };
+        if let Some((cached, string)) = slot.get() {
+            if cached == symbol {
+                // SAFETY: The cached pointer was created from a string resolved by
+                //         the bucket backend which never moves or frees its strings
+                //         while it is alive.
+                return Some(unsafe { string.as_ref() });
+            }
+        }
+        let string = self.interner.resolve(symbol)?;
+        slot.set(Some((symbol, NonNull::from(string))));
+        Some(string)
+    }
+
+    /// Removes all entries from the cache.
+    pub fn clear_cache(&self) {
+        for slot in self.cache.iter() {
+            slot.set(None);
+        }
+    }
+
+    /// Returns the cache slot for the given `symbol` if caching is enabled.
+    #[inline]
+    fn slot(&self, symbol: S) -> Option<&Slot<S>> {
+        let index = symbol.to_usize().checked_rem(self.cache.len())?;
+        self.cache.get(index)
+    }
+}
+
+impl<'i, S, H> From<StringInterner<'i, BucketBackend<'i, S>, H>> for CachedInterner<'i, S, H>
+where
+    S: Symbol,
+    H: BuildHasher,
+{
+    #[inline]
+    fn from(interner: StringInterner<'i, BucketBackend<'i, S>, H>) -> Self {
+        Self::new(interner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, vec::Vec};
+
+    #[test]
+    fn resolve_works() {
+        let mut interner = <CachedInterner>::with_cache_size(StringInterner::new(), 4);
+        let symbols = (0..20)
+            .map(|i| interner.get_or_intern(format!("string-{}", i)))
+            .collect::<Vec<_>>();
+        for _ in 0..2 {
+            for (i, &symbol) in symbols.iter().enumerate() {
+                assert_eq!(interner.resolve(symbol), Some(format!("string-{}", i).as_str()));
+            }
+        }
+        let invalid = DefaultSymbol::try_from_usize(20).unwrap();
+        assert_eq!(interner.resolve(invalid), None);
+    }
+
+    #[test]
+    fn cache_hit_works() {
+        let mut interner = <CachedInterner>::new(StringInterner::new());
+        let aa = interner.get_or_intern("aa");
+        let first = interner.resolve(aa).unwrap().as_ptr();
+        // Interning many strings allocates new buckets but must not
+        // invalidate the cached string.
+        for i in 0..100 {
+            interner.get_or_intern(format!("string-{}", i));
+        }
+        let second = interner.resolve(aa).unwrap();
+        assert_eq!(first, second.as_ptr());
+        assert_eq!(second, "aa");
+        interner.clear_cache();
+        assert_eq!(interner.resolve(aa), Some("aa"));
+    }
+
+    #[test]
+    fn zero_cache_size_works() {
+        let mut interner = <CachedInterner>::with_cache_size(StringInterner::new(), 0);
+        assert_eq!(interner.cache_size(), 0);
+        let aa = interner.get_or_intern("aa");
+        assert_eq!(interner.resolve(aa), Some("aa"));
+        assert_eq!(interner.into_inner().len(), 1);
+    }
+}
@@ -0,0 +1,181 @@
+use core::{
+    fmt,
+    fmt::{Display, Formatter},
+};
+
+/// Error returned when a string exceeds the capacity accepted by an interner.
+///
+/// See [`StringInterner::set_max_string_len`](crate::StringInterner::set_max_string_len).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ExceedsCapacityError {
+    len: usize,
+    capacity: usize,
+}
+
+impl ExceedsCapacityError {
+    /// Creates a new error for a string of `len` bytes exceeding `capacity`.
+    #[inline]
+    pub(crate) fn new(len: usize, capacity: usize) -> Self {
+        Self { len, capacity }
+    }
+
+    /// Returns the length in bytes of the rejected string.
+    #[inline]
+    pub fn string_len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the maximum accepted length in bytes.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl Display for ExceedsCapacityError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "string of {} bytes exceeds the capacity of {} bytes",
+            self.len, self.capacity
+        )
+    }
+}
+
+#[cfg(feature = "error-in-core")]
+impl core::error::Error for ExceedsCapacityError {}
+
+/// Error returned when an index cannot be represented by a symbol type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct OutOfBoundsError {
+    index: usize,
+    max_index: usize,
+}
+
+impl OutOfBoundsError {
+    /// Creates a new error for `index` exceeding `max_index`.
+    #[inline]
+    pub(crate) fn new(index: usize, max_index: usize) -> Self {
+        Self { index, max_index }
+    }
+
+    /// Returns the index that is out of bounds.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the maximum index representable by the symbol type.
+    #[inline]
+    pub fn max_index(&self) -> usize {
+        self.max_index
+    }
+}
+
+impl Display for OutOfBoundsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "index {} is out of bounds for symbols with a maximum index of {}",
+            self.index, self.max_index
+        )
+    }
+}
+
+#[cfg(feature = "error-in-core")]
+impl core::error::Error for OutOfBoundsError {}
+
+/// Error returned when a symbol does not refer to an interned string.
+///
+/// See [`StringInterner::try_resolve`](crate::StringInterner::try_resolve).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct InvalidSymbolError {
+    index: usize,
+    len: usize,
+}
+
+impl InvalidSymbolError {
+    /// Creates a new error for the symbol `index` of an interner with `len` strings.
+    #[inline]
+    pub(crate) fn new(index: usize, len: usize) -> Self {
+        Self { index, len }
+    }
+
+    /// Returns the `usize` representation of the invalid symbol.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the number of strings interned at the time of the error.
+    #[inline]
+    pub fn interner_len(&self) -> usize {
+        self.len
+    }
+}
+
+impl Display for InvalidSymbolError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "symbol {} does not refer to any of the {} interned strings",
+            self.index, self.len
+        )
+    }
+}
+
+#[cfg(feature = "error-in-core")]
+impl core::error::Error for InvalidSymbolError {}
+
+/// Errors returned by the fallible APIs of the [`StringInterner`](crate::StringInterner).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum InternerError {
+    /// A string exceeds the capacity accepted by the interner.
+    CapacityExceeded(ExceedsCapacityError),
+    /// An index cannot be represented by the symbol type of the interner.
+    SymbolOutOfBounds(OutOfBoundsError),
+    /// A symbol does not refer to an interned string.
+    InvalidSymbol(InvalidSymbolError),
+}
+
+impl From<ExceedsCapacityError> for InternerError {
+    #[inline]
+    fn from(error: ExceedsCapacityError) -> Self {
+        Self::CapacityExceeded(error)
+    }
+}
+
+impl From<OutOfBoundsError> for InternerError {
+    #[inline]
+    fn from(error: OutOfBoundsError) -> Self {
+        Self::SymbolOutOfBounds(error)
+    }
+}
+
+impl From<InvalidSymbolError> for InternerError {
+    #[inline]
+    fn from(error: InvalidSymbolError) -> Self {
+        Self::InvalidSymbol(error)
+    }
+}
+
+impl Display for InternerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CapacityExceeded(error) => Display::fmt(error, f),
+            Self::SymbolOutOfBounds(error) => Display::fmt(error, f),
+            Self::InvalidSymbol(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+#[cfg(feature = "error-in-core")]
+impl core::error::Error for InternerError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::CapacityExceeded(error) => Some(error),
+            Self::SymbolOutOfBounds(error) => Some(error),
+            Self::InvalidSymbol(error) => Some(error),
+        }
+    }
+}
@@ -1,11 +1,31 @@
-use crate::{backend::Backend, Symbol};
+#[cfg(feature = "backends")]
+use crate::backend::{BucketBackend, Checkpoint, GroupId};
+#[cfg(feature = "small-string-cache")]
+use crate::small::SmallStrings;
+#[cfg(any(feature = "generations", feature = "store-hashes"))]
+use crate::side_table::SideTable;
+#[cfg(feature = "stats")]
+use crate::InternStats;
+use crate::{
+    backend::{Backend, RebindSymbol},
+    error::{ExceedsCapacityError, InternerError, InvalidSymbolError, OutOfBoundsError},
+    CapacityReport,
+    Symbol,
+    SymbolMap,
+};
+use alloc::{borrow::Cow, boxed::Box, string::String, sync::Arc, vec::Vec};
 use core::{
     fmt,
-    fmt::{Debug, Formatter},
+    fmt::{Debug, Display, Formatter},
     hash::{BuildHasher, Hash, Hasher},
-    iter::FromIterator,
+    iter::{FromIterator, Skip},
+    ops::Deref,
+    str::Utf8Error,
 };
-use hashbrown::{DefaultHashBuilder, HashMap};
+use hashbrown::{hash_table::Entry, DefaultHashBuilder, HashTable};
+
+/// Key of a `'static` string in the pointer-keyed side table: its address and length.
+type StaticKey = (usize, usize);
 
 /// Creates the `u64` hash value for the given value using the given hash builder.
 fn make_hash<T>(builder: &impl BuildHasher, value: &T) -> u64
@@ -17,6 +37,218 @@ where
     state.finish()
 }
 
+/// Creates the `u64` hash value of `string` with all ASCII letters folded to lowercase.
+///
+/// Strings that are equal ignoring ASCII case have the same hash value.
+fn make_folded_hash(builder: &impl BuildHasher, string: &str) -> u64 {
+    let state = &mut builder.build_hasher();
+    let mut chunk = [0_u8; 32];
+    for bytes in string.as_bytes().chunks(chunk.len()) {
+        let chunk = &mut chunk[..bytes.len()];
+        chunk.copy_from_slice(bytes);
+        chunk.make_ascii_lowercase();
+        state.write(chunk);
+    }
+    state.write_usize(string.len());
+    state.finish()
+}
+
+/// Registers `symbol` for `string` with the case-folded index unless a string
+/// equal to it ignoring ASCII case is registered already.
+///
+/// # Safety
+///
+/// All symbols of `folded` must be valid for the `backend`.
+unsafe fn index_folded<'i, B>(
+    folded: &mut HashTable<<B as Backend<'i>>::Symbol>,
+    builder: &impl BuildHasher,
+    backend: &B,
+    string: &str,
+    symbol: <B as Backend<'i>>::Symbol,
+) where
+    B: Backend<'i>,
+{
+    let hash = make_folded_hash(builder, string);
+    let entry = folded.entry(
+        hash,
+        |other| {
+            // SAFETY: The caller guarantees that the symbols of `folded` are valid.
+            string.eq_ignore_ascii_case(unsafe { backend.resolve_unchecked(*other) }.as_ref())
+        },
+        |other| {
+            // SAFETY: The caller guarantees that the symbols of `folded` are valid.
+            let other = unsafe { backend.resolve_unchecked(*other) };
+            make_folded_hash(builder, other.as_ref())
+        },
+    );
+    if let Entry::Vacant(vacant) = entry {
+        vacant.insert(symbol);
+    }
+}
+
+/// Creates the `u64` hash value of the NFC normalized form of `string`.
+///
+/// Strings that are canonically equivalent have the same hash value. Strings
+/// that are already in NFC are hashed without normalizing them.
+#[cfg(feature = "unicode")]
+fn make_nfc_hash(builder: &impl BuildHasher, string: &str) -> u64 {
+    use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+
+    let state = &mut builder.build_hasher();
+    if is_nfc_quick(string.chars()) == IsNormalized::Yes {
+        string.chars().for_each(|c| state.write_u32(c.into()));
+    } else {
+        string.nfc().for_each(|c| state.write_u32(c.into()));
+    }
+    state.finish()
+}
+
+/// Returns `true` if `a` and `b` are canonically equivalent.
+#[cfg(feature = "unicode")]
+fn eq_nfc(a: &str, b: &str) -> bool {
+    use unicode_normalization::UnicodeNormalization;
+
+    a == b || a.nfc().eq(b.nfc())
+}
+
+/// Registers `symbol` for `string` with the NFC index unless a string
+/// canonically equivalent to it is registered already.
+///
+/// # Safety
+///
+/// All symbols of `nfc` must be valid for the `backend`.
+#[cfg(feature = "unicode")]
+unsafe fn index_nfc<'i, B>(
+    nfc: &mut HashTable<<B as Backend<'i>>::Symbol>,
+    builder: &impl BuildHasher,
+    backend: &B,
+    string: &str,
+    symbol: <B as Backend<'i>>::Symbol,
+) where
+    B: Backend<'i>,
+{
+    let hash = make_nfc_hash(builder, string);
+    let entry = nfc.entry(
+        hash,
+        |other| {
+            // SAFETY: The caller guarantees that the symbols of `nfc` are valid.
+            eq_nfc(string, unsafe { backend.resolve_unchecked(*other) }.as_ref())
+        },
+        |other| {
+            // SAFETY: The caller guarantees that the symbols of `nfc` are valid.
+            let other = unsafe { backend.resolve_unchecked(*other) };
+            make_nfc_hash(builder, other.as_ref())
+        },
+    );
+    if let Entry::Vacant(vacant) = entry {
+        vacant.insert(symbol);
+    }
+}
+
+/// Creates the `u64` hash value of the string the `symbol` resolves to in `backend`.
+///
+/// # Safety
+///
+/// The `symbol` must be valid for the `backend`.
+unsafe fn make_symbol_hash<'i, B>(
+    builder: &impl BuildHasher,
+    backend: &B,
+    symbol: <B as Backend<'i>>::Symbol,
+) -> u64
+where
+    B: Backend<'i>,
+{
+    // SAFETY: The function is marked unsafe so that the caller guarantees
+    //         that required invariants are checked.
+    let string = unsafe { backend.resolve_unchecked(symbol) };
+    make_hash(builder, string.as_ref())
+}
+
+/// Strategy of [`StringInterner::get_or_intern_using`] to intern a string
+/// into the backend if it has not been interned yet.
+trait InternWith<'i, B, T>
+where
+    B: Backend<'i>,
+{
+    /// Interns `string` into `backend` and returns its symbol.
+    ///
+    /// # Safety
+    ///
+    /// See the implementors.
+    #[track_caller]
+    unsafe fn intern(self, backend: &mut B, string: T) -> <B as Backend<'i>>::Symbol;
+}
+
+/// Interns strings via [`Backend::intern`].
+struct InternCopied;
+
+impl<'i, 'a, B> InternWith<'i, B, &'a str> for InternCopied
+where
+    B: Backend<'i>,
+{
+    /// # Safety
+    ///
+    /// This has no safety requirements.
+    #[inline]
+    unsafe fn intern(self, backend: &mut B, string: &'a str) -> <B as Backend<'i>>::Symbol {
+        backend.intern(string)
+    }
+}
+
+/// Interns strings via [`Backend::intern_static`].
+struct InternStatic;
+
+impl<'i, B> InternWith<'i, B, &'static str> for InternStatic
+where
+    B: Backend<'i>,
+{
+    /// # Safety
+    ///
+    /// This has no safety requirements.
+    #[inline]
+    unsafe fn intern(self, backend: &mut B, string: &'static str) -> <B as Backend<'i>>::Symbol {
+        backend.intern_static(string)
+    }
+}
+
+/// Interns strings via [`BucketBackend::intern_borrowed`].
+#[cfg(feature = "backends")]
+struct InternBorrowed;
+
+#[cfg(feature = "backends")]
+impl<'i, 'a, S> InternWith<'i, BucketBackend<'i, S>, &'a str> for InternBorrowed
+where
+    S: Symbol,
+{
+    /// # Safety
+    ///
+    /// See [`BucketBackend::intern_borrowed`].
+    #[inline]
+    unsafe fn intern(self, backend: &mut BucketBackend<'i, S>, string: &'a str) -> S {
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        unsafe { backend.intern_borrowed(string) }
+    }
+}
+
+/// Interns strings via [`BucketBackend::intern_grouped`] into the given group.
+#[cfg(feature = "backends")]
+struct InternGrouped(GroupId);
+
+#[cfg(feature = "backends")]
+impl<'i, 'a, S> InternWith<'i, BucketBackend<'i, S>, &'a str> for InternGrouped
+where
+    S: Symbol,
+{
+    /// # Safety
+    ///
+    /// This has no safety requirements.
+    #[inline]
+    unsafe fn intern(self, backend: &mut BucketBackend<'i, S>, string: &'a str) -> S {
+        backend.intern_grouped(self.0, string)
+    }
+}
+
 /// Data structure to intern and resolve strings.
 ///
 /// Caches strings efficiently, with minimal memory footprint and associates them with unique symbols.
@@ -32,9 +264,41 @@ pub struct StringInterner<'i, B, H = DefaultHashBuilder>
 where
     B: Backend<'i>,
 {
-    dedup: HashMap<<B as Backend<'i>>::Symbol, (), ()>,
+    dedup: HashTable<<B as Backend<'i>>::Symbol>,
+    statics: HashTable<(StaticKey, <B as Backend<'i>>::Symbol)>,
     hasher: H,
     backend: B,
+    max_string_len: Option<usize>,
+    /// Whether `get_or_intern` deduplicates strings, see [`StringInterner::new_nodedup`].
+    deduplicate: bool,
+    /// The number of strings interned without deduplication.
+    appended: usize,
+    /// Whether a string has been removed, after which the symbols no longer
+    /// reflect the insertion order, see [`StringInterner::iter_insertion_order`].
+    removed: bool,
+    /// Index of the first-seen symbols by their ASCII case-folded strings.
+    ///
+    /// Created by the first ASCII case-insensitive lookup.
+    folded: Option<HashTable<<B as Backend<'i>>::Symbol>>,
+    /// Index of the first-seen symbols by their NFC normalized strings.
+    ///
+    /// Created by the first NFC lookup.
+    #[cfg(feature = "unicode")]
+    nfc: Option<HashTable<<B as Backend<'i>>::Symbol>>,
+    #[cfg(feature = "small-string-cache")]
+    small: SmallStrings<<B as Backend<'i>>::Symbol>,
+    /// The generation assigned to the next newly interned string.
+    #[cfg(feature = "generations")]
+    generation: u64,
+    /// The generations of the interned strings.
+    #[cfg(feature = "generations")]
+    generations: SideTable<<B as Backend<'i>>::Symbol, u64>,
+    /// The hashes of the interned strings computed by `hasher`.
+    #[cfg(feature = "store-hashes")]
+    hashes: SideTable<<B as Backend<'i>>::Symbol, u64>,
+    /// The hits and misses of deduplicating lookups when interning.
+    #[cfg(feature = "stats")]
+    stats: InternStats,
 }
 
 impl<'i, B, H> Debug for StringInterner<'i, B, H>
@@ -47,6 +311,8 @@ where
         f.debug_struct("StringInterner")
             .field("dedup", &self.dedup)
             .field("backend", &self.backend)
+            .field("max_string_len", &self.max_string_len)
+            .field("deduplicate", &self.deduplicate)
             .finish()
     }
 }
@@ -68,8 +334,26 @@ where
     fn clone(&self) -> Self {
         Self {
             dedup: self.dedup.clone(),
+            statics: self.statics.clone(),
             hasher: self.hasher.clone(),
             backend: self.backend.clone(),
+            max_string_len: self.max_string_len,
+            deduplicate: self.deduplicate,
+            appended: self.appended,
+            removed: self.removed,
+            folded: self.folded.clone(),
+            #[cfg(feature = "unicode")]
+            nfc: self.nfc.clone(),
+            #[cfg(feature = "small-string-cache")]
+            small: self.small.clone(),
+            #[cfg(feature = "generations")]
+            generation: self.generation,
+            #[cfg(feature = "generations")]
+            generations: self.generations.clone(),
+            #[cfg(feature = "store-hashes")]
+            hashes: self.hashes.clone(),
+            #[cfg(feature = "stats")]
+            stats: self.stats,
         }
     }
 }
@@ -102,21 +386,129 @@ where
     /// Creates a new empty `StringInterner`.
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn new() -> Self {
-        Self {
-            dedup: HashMap::default(),
-            hasher: Default::default(),
-            backend: B::default(),
-        }
+        Self::from_parts_unchecked(B::default(), Default::default(), 0)
     }
 
     /// Creates a new `StringInterner` with the given initial capacity.
     #[cfg_attr(feature = "inline-more", inline)]
+    #[track_caller]
     pub fn with_capacity(cap: usize) -> Self {
+        Self::from_parts_unchecked(B::with_capacity(cap), Default::default(), cap)
+    }
+
+    /// Creates a new empty `StringInterner` that does not deduplicate strings.
+    ///
+    /// Every call to [`get_or_intern`][StringInterner::get_or_intern] and its
+    /// variants interns the string anew and returns a fresh symbol, turning the
+    /// interner into an append-only store of strings. No hash map is maintained
+    /// which saves its memory and the cost of hashing every string.
+    ///
+    /// # Note
+    ///
+    /// Without the hash map [`get`][StringInterner::get] has to scan all
+    /// interned strings and takes linear time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::DefaultStringInterner;
+    /// let mut interner = <DefaultStringInterner>::new_nodedup();
+    /// let a = interner.get_or_intern("log line");
+    /// let b = interner.get_or_intern("log line");
+    /// assert_ne!(a, b);
+    /// assert_eq!(interner.len(), 2);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new_nodedup() -> Self {
         Self {
-            dedup: HashMap::with_capacity_and_hasher(cap, ()),
-            hasher: Default::default(),
-            backend: B::with_capacity(cap),
+            deduplicate: false,
+            ..Self::new()
+        }
+    }
+
+    /// Creates a new `StringInterner` with the given initial capacity.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InternerError::SymbolOutOfBounds`] if the symbol type cannot
+    /// represent `cap` distinct symbols.
+    pub fn try_with_capacity(cap: usize) -> Result<Self, InternerError> {
+        let max_index = <B as Backend<'i>>::Symbol::MAX_INDEX;
+        match cap.checked_sub(1) {
+            Some(index) if index > max_index => {
+                Err(OutOfBoundsError::new(index, max_index).into())
+            }
+            _ => Ok(Self::with_capacity(cap)),
+        }
+    }
+
+    /// Creates a new `StringInterner` wrapping the given backend.
+    ///
+    /// This allows to use backend specific constructors for custom initial
+    /// sizing. Strings already interned by `backend` are registered with the
+    /// interner.
+    ///
+    /// # Note
+    ///
+    /// If `backend` interned the same string multiple times only the first of
+    /// its symbols is returned by [`StringInterner::get`] and the duplicates are
+    /// not accounted for by [`StringInterner::len`].
+    pub fn from_backend(backend: B) -> Self {
+        Self::from_backend_and_hasher(backend, Default::default())
+    }
+
+    /// Creates a new `StringInterner` from strings that are known to be unique.
+    ///
+    /// The strings are interned in iteration order and registered with the
+    /// internal hash map without checking whether they have been interned
+    /// before. This saves a lookup per string when loading pre-deduplicated
+    /// input, for example a sorted list of identifiers without repetitions.
+    ///
+    /// # Note
+    ///
+    /// The strings do not have to be sorted. Passing the same string multiple
+    /// times is not undefined behavior but interns it multiple times: every
+    /// occurrence receives its own symbol, [`StringInterner::len`] counts each
+    /// of them and [`StringInterner::get`] returns any one of them.
+    ///
+    /// # Panics
+    ///
+    /// If the symbol type cannot represent all strings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::DefaultStringInterner;
+    /// let interner = <DefaultStringInterner>::from_sorted_unique(["a", "b", "c"]);
+    /// assert_eq!(interner.len(), 3);
+    /// assert_eq!(interner.resolve(interner.get("b").unwrap()), Some("b"));
+    /// ```
+    #[track_caller]
+    pub fn from_sorted_unique<T, I>(strings: I) -> Self
+    where
+        T: AsRef<str>,
+        I: IntoIterator<Item = T>,
+    {
+        let strings = strings.into_iter();
+        let mut interner = Self::with_capacity(strings.size_hint().0);
+        for string in strings {
+            let string = string.as_ref();
+            let Self {
+                dedup,
+                hasher,
+                backend,
+                ..
+            } = &mut interner;
+            let symbol = backend.intern(string);
+            let hash = make_hash(hasher, string);
+            dedup.insert_unique(hash, symbol, |symbol| {
+                // SAFETY: This is safe because we only operate on symbols that
+                //         we receive from our backend making them valid.
+                unsafe { make_symbol_hash(hasher, backend, *symbol) }
+            });
+            interner.register_new(symbol, Some(hash));
         }
+        interner
     }
 }
 
@@ -129,27 +521,132 @@ where
     /// Creates a new empty `StringInterner` with the given hasher.
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn with_hasher(hash_builder: H) -> Self {
-        StringInterner {
-            dedup: HashMap::default(),
-            hasher: hash_builder,
-            backend: B::default(),
-        }
+        Self::from_parts_unchecked(B::default(), hash_builder, 0)
     }
 
     /// Creates a new empty `StringInterner` with the given initial capacity and the given hasher.
     #[cfg_attr(feature = "inline-more", inline)]
+    #[track_caller]
     pub fn with_capacity_and_hasher(cap: usize, hash_builder: H) -> Self {
-        StringInterner {
-            dedup: HashMap::with_capacity_and_hasher(cap, ()),
+        Self::from_parts_unchecked(B::with_capacity(cap), hash_builder, cap)
+    }
+
+    /// Creates a new `StringInterner` wrapping the given backend and using the given hasher.
+    ///
+    /// See [`StringInterner::from_backend`] for details.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn from_backend_and_hasher(backend: B, hash_builder: H) -> Self {
+        Self::from_parts(backend, hash_builder, 0)
+    }
+
+    /// Creates a new `StringInterner` from its parts without registering the
+    /// strings already interned by `backend`.
+    ///
+    /// The internal hash map is allocated for at least `cap` strings. All
+    /// constructors go through this so that every field is initialized once.
+    #[inline]
+    fn from_parts_unchecked(backend: B, hash_builder: H, cap: usize) -> Self {
+        Self {
+            dedup: HashTable::with_capacity(cap),
+            statics: HashTable::new(),
             hasher: hash_builder,
-            backend: B::with_capacity(cap),
+            backend,
+            max_string_len: None,
+            deduplicate: true,
+            appended: 0,
+            removed: false,
+            folded: None,
+            #[cfg(feature = "unicode")]
+            nfc: None,
+            #[cfg(feature = "small-string-cache")]
+            small: SmallStrings::default(),
+            #[cfg(feature = "generations")]
+            generation: 0,
+            #[cfg(feature = "generations")]
+            generations: SideTable::default(),
+            #[cfg(feature = "store-hashes")]
+            hashes: SideTable::default(),
+            #[cfg(feature = "stats")]
+            stats: InternStats::default(),
+        }
+    }
+
+    /// Creates a new `StringInterner` from its parts.
+    ///
+    /// The internal hash map is allocated for at least `cap` strings.
+    pub(crate) fn from_parts(backend: B, hash_builder: H, cap: usize) -> Self {
+        let mut interner = Self::from_parts_unchecked(backend, hash_builder, cap);
+        interner.rebuild_dedup();
+        #[cfg(feature = "generations")]
+        {
+            let Self {
+                backend,
+                generation,
+                generations,
+                ..
+            } = &mut interner;
+            for (symbol, _) in backend.iter() {
+                generations.insert(symbol, *generation);
+                *generation += 1;
+            }
+        }
+        interner
+    }
+
+    /// Decomposes the `StringInterner` into its backend, hasher and deduplicated symbols.
+    #[cfg(feature = "perfect-hash")]
+    pub(crate) fn into_parts(mut self) -> (B, H, HashTable<<B as Backend<'i>>::Symbol>) {
+        if !self.deduplicate {
+            self.rebuild_dedup();
+        }
+        let Self {
+            dedup,
+            hasher,
+            backend,
+            ..
+        } = self;
+        (backend, hasher, dedup)
+    }
+
+    /// Rebuilds the internal hash map used for deduplication from the backend.
+    fn rebuild_dedup(&mut self) {
+        let Self {
+            dedup,
+            hasher,
+            backend,
+            #[cfg(feature = "store-hashes")]
+            hashes,
+            ..
+        } = self;
+        dedup.clear();
+        for (symbol, string) in backend.iter() {
+            let string = string.as_ref();
+            let hash = make_hash(hasher, string);
+            #[cfg(feature = "store-hashes")]
+            hashes.insert(symbol, hash);
+            let entry = dedup.entry(
+                hash,
+                |symbol| {
+                    // SAFETY: This is safe because we only operate on symbols that
+                    //         we receive from our backend making them valid.
+                    string == unsafe { backend.resolve_unchecked(*symbol) }.as_ref()
+                },
+                |symbol| {
+                    // SAFETY: This is safe because we only operate on symbols that
+                    //         we receive from our backend making them valid.
+                    unsafe { make_symbol_hash(hasher, backend, *symbol) }
+                },
+            );
+            if let Entry::Vacant(vacant) = entry {
+                vacant.insert(symbol);
+            }
         }
     }
 
     /// Returns the number of strings interned by the interner.
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn len(&self) -> usize {
-        self.dedup.len()
+        self.dedup.len() + self.appended
     }
 
     /// Returns `true` if the string interner has no interned strings.
@@ -158,140 +655,2044 @@ where
         self.len() == 0
     }
 
+    /// Returns `true` if the interner deduplicates interned strings.
+    ///
+    /// This is the case unless it was created by [`StringInterner::new_nodedup`].
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn deduplicates(&self) -> bool {
+        self.deduplicate
+    }
+
+    /// Returns the generation that is assigned to the next newly interned string.
+    ///
+    /// Every newly interned string is assigned the current generation which is
+    /// then incremented. Looking up strings that have already been interned
+    /// does not change the generation. Rollbacks do not reset it either so that
+    /// generations stay unique.
+    #[cfg(feature = "generations")]
+    #[inline]
+    pub fn current_generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns the generation in which the string for `symbol` has been interned if any.
+    ///
+    /// Older strings have lower generations which allows implementing eviction
+    /// policies based on the relative age of interned strings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::DefaultStringInterner;
+    /// let mut interner = <DefaultStringInterner>::new();
+    /// let a = interner.get_or_intern("a");
+    /// let b = interner.get_or_intern("b");
+    /// interner.get_or_intern("a");
+    /// assert_eq!(interner.generation_of(a), Some(0));
+    /// assert_eq!(interner.generation_of(b), Some(1));
+    /// assert_eq!(interner.current_generation(), 2);
+    /// ```
+    #[cfg(feature = "generations")]
+    #[inline]
+    pub fn generation_of(&self, symbol: <B as Backend<'i>>::Symbol) -> Option<u64> {
+        self.generations.get(symbol).copied()
+    }
+
+    /// Returns the hits and misses of deduplicating lookups when interning.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::DefaultStringInterner;
+    /// let mut interner = <DefaultStringInterner>::new();
+    /// interner.get_or_intern("a");
+    /// interner.get_or_intern("b");
+    /// interner.get_or_intern("a");
+    /// let stats = interner.stats();
+    /// assert_eq!((stats.hits, stats.misses), (1, 2));
+    /// ```
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn stats(&self) -> InternStats {
+        self.stats
+    }
+
+    /// Resets the hits and misses returned by [`StringInterner::stats`] to zero.
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn reset_stats(&mut self) {
+        self.stats = InternStats::default();
+    }
+
+    /// Returns the maximum length in bytes of strings accepted for interning if any.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn max_string_len(&self) -> Option<usize> {
+        self.max_string_len
+    }
+
+    /// Sets the maximum length in bytes of strings accepted for interning.
+    ///
+    /// Strings longer than the limit are rejected:
+    /// [`get_or_intern`][StringInterner::get_or_intern] panics on them while
+    /// [`try_get_or_intern`][StringInterner::try_get_or_intern] returns
+    /// [`InternerError::CapacityExceeded`]. The limit also applies to strings
+    /// that have already been interned.
+    ///
+    /// # Note
+    ///
+    /// Most methods check the length before interning anything. Methods that
+    /// assemble the string in the backend, such as
+    /// [`get_or_intern_fmt`][StringInterner::get_or_intern_fmt] and
+    /// [`get_or_intern_chars`][StringInterner::get_or_intern_chars], can only
+    /// check it afterwards. They roll the backend back before panicking, but
+    /// a bucket may have been allocated for the string by then.
+    ///
+    /// Setting the limit to `None` (the default) disables it.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn set_max_string_len(&mut self, max_string_len: Option<usize>) {
+        self.max_string_len = max_string_len;
+    }
+
+    /// Returns `true` if `string` does not exceed the maximum string length.
+    #[inline]
+    fn is_within_max_len(&self, string: &str) -> bool {
+        self.max_string_len.is_none_or(|max| string.len() <= max)
+    }
+
     /// Returns the symbol for the given string if any.
     ///
     /// Can be used to query if a string has already been interned without interning.
+    ///
+    /// Prefers the answer of [`Backend::get`] if the backend provides one.
     #[inline]
     pub fn get<T>(&self, string: T) -> Option<<B as Backend<'i>>::Symbol>
     where
         T: AsRef<str>,
     {
         let string = string.as_ref();
+        if let Some(symbol) = self.backend.get(string) {
+            return Some(symbol);
+        }
+        if !self.deduplicate {
+            return self
+                .backend
+                .iter()
+                .find(|(_, other)| other.as_ref() == string)
+                .map(|(symbol, _)| symbol);
+        }
+        #[cfg(feature = "small-string-cache")]
+        if let Some(symbol) = self.small.get(string) {
+            return Some(symbol);
+        }
         let Self {
             dedup,
             hasher,
             backend,
+            ..
         } = self;
         let hash = make_hash(hasher, string);
         dedup
-            .raw_entry()
-            .from_hash(hash, |symbol| {
+            .find(hash, |symbol| {
                 // SAFETY: This is safe because we only operate on symbols that
                 //         we receive from our backend making them valid.
                 string == unsafe { backend.resolve_unchecked(*symbol) }.as_ref()
             })
-            .map(|(&symbol, &())| symbol)
+            .copied()
     }
 
     /// Interns the given string.
     ///
     /// This is used as backend by [`get_or_intern`][1] and [`get_or_intern_static`][2].
+    /// The string is interned with the strategy `with` so that panics of the
+    /// backend are reported at the location of the caller.
+    ///
+    /// # Safety
+    ///
+    /// The safety requirements of `W` must be met for `string`.
     ///
     /// [1]: [`StringInterner::get_or_intern`]
     /// [2]: [`StringInterner::get_or_intern_static`]
     #[cfg_attr(feature = "inline-more", inline)]
-    fn get_or_intern_using<T>(
+    #[track_caller]
+    unsafe fn get_or_intern_using<W, T>(
         &mut self,
+        with: W,
         string: T,
-        intern_fn: fn(&mut B, T) -> <B as Backend<'i>>::Symbol,
     ) -> <B as Backend<'i>>::Symbol
     where
+        W: InternWith<'i, B, T>,
         T: Copy + Hash + AsRef<str> + for<'a> PartialEq<&'a str>,
     {
+        if !self.deduplicate {
+            // SAFETY: The caller guarantees the safety requirements of `W`.
+            let symbol = unsafe { with.intern(&mut self.backend, string) };
+            self.appended += 1;
+            #[cfg(feature = "stats")]
+            self.stats.record(false);
+            self.register_new(symbol, None);
+            return symbol;
+        }
+        #[cfg(feature = "small-string-cache")]
+        if let Some(symbol) = self.small.get(string.as_ref()) {
+            #[cfg(feature = "stats")]
+            self.stats.record(true);
+            return symbol;
+        }
         let Self {
             dedup,
             hasher,
             backend,
+            ..
         } = self;
         let hash = make_hash(hasher, string.as_ref());
-        let entry = dedup.raw_entry_mut().from_hash(hash, |symbol| {
-            // SAFETY: This is safe because we only operate on symbols that
-            //         we receive from our backend making them valid.
-            string == unsafe { backend.resolve_unchecked(*symbol) }.as_ref()
-        });
-        use hashbrown::hash_map::RawEntryMut;
-        let (&mut symbol, &mut ()) = match entry {
-            RawEntryMut::Occupied(occupied) => occupied.into_key_value(),
-            RawEntryMut::Vacant(vacant) => {
-                let symbol = intern_fn(backend, string);
-                vacant.insert_with_hasher(hash, symbol, (), |symbol| {
-                    // SAFETY: This is safe because we only operate on symbols that
-                    //         we receive from our backend making them valid.
-                    let string = unsafe { backend.resolve_unchecked(*symbol) };
-                    make_hash(hasher, string.as_ref())
-                })
+        let entry = dedup.entry(
+            hash,
+            |symbol| {
+                // SAFETY: This is safe because we only operate on symbols that
+                //         we receive from our backend making them valid.
+                string == unsafe { backend.resolve_unchecked(*symbol) }.as_ref()
+            },
+            |symbol| {
+                // SAFETY: This is safe because we only operate on symbols that
+                //         we receive from our backend making them valid.
+                unsafe { make_symbol_hash(hasher, backend, *symbol) }
+            },
+        );
+        #[cfg(feature = "stats")]
+        self.stats.record(matches!(entry, Entry::Occupied(_)));
+        let symbol = match entry {
+            Entry::Occupied(occupied) => *occupied.get(),
+            Entry::Vacant(vacant) => {
+                // SAFETY: The caller guarantees the safety requirements of `W`.
+                let symbol = unsafe { with.intern(backend, string) };
+                vacant.insert(symbol);
+                #[cfg(feature = "validate")]
+                self.debug_assert_unique(symbol);
+                self.register_new(symbol, Some(hash));
+                symbol
             }
         };
+        #[cfg(feature = "small-string-cache")]
+        self.small.insert(string.as_ref(), symbol);
         symbol
     }
 
+    /// Asserts that no other symbol resolves to the string of the newly
+    /// deduplicated `symbol` in debug builds.
+    ///
+    /// This catches deduplication bugs, for example due to inconsistent hashes,
+    /// at the cost of a linear scan over all interned strings. Interners with
+    /// more than 4096 strings are not scanned so that interning stays linear.
+    #[cfg(feature = "validate")]
+    fn debug_assert_unique(&self, symbol: <B as Backend<'i>>::Symbol) {
+        if cfg!(debug_assertions) && self.len() <= 4096 {
+            // SAFETY: The symbol has just been created by the backend.
+            let string = unsafe { self.backend.resolve_unchecked(symbol) };
+            let string = string.as_ref();
+            let duplicate = self
+                .backend
+                .iter()
+                .find(|(other, other_string)| *other != symbol && other_string.as_ref() == string);
+            if let Some((other, _)) = duplicate {
+                panic!(
+                    "deduplication failed: symbols {} and {} resolve to the same string",
+                    other.to_usize(),
+                    symbol.to_usize()
+                );
+            }
+        }
+    }
+
+    /// Registers the newly interned `symbol` with the auxiliary indices.
+    ///
+    /// The `hash` of the string is passed if it has been computed already.
+    #[cfg_attr(not(feature = "store-hashes"), allow(unused_variables))]
+    fn register_new(&mut self, symbol: <B as Backend<'i>>::Symbol, hash: Option<u64>) {
+        #[cfg(feature = "generations")]
+        {
+            self.generations.insert(symbol, self.generation);
+            self.generation += 1;
+        }
+        #[cfg(feature = "store-hashes")]
+        {
+            let hash = hash.unwrap_or_else(|| {
+                // SAFETY: The symbol has just been created by the backend.
+                unsafe { make_symbol_hash(&self.hasher, &self.backend, symbol) }
+            });
+            self.hashes.insert(symbol, hash);
+        }
+        let Self {
+            hasher,
+            backend,
+            folded,
+            ..
+        } = self;
+        if let Some(folded) = folded {
+            // SAFETY: This is safe because we only operate on symbols that
+            //         we receive from our backend making them valid.
+            unsafe {
+                let string = backend.resolve_unchecked(symbol);
+                index_folded(folded, hasher, backend, string.as_ref(), symbol)
+            };
+        }
+        #[cfg(feature = "unicode")]
+        if let Some(nfc) = &mut self.nfc {
+            // SAFETY: This is safe because we only operate on symbols that
+            //         we receive from our backend making them valid.
+            unsafe {
+                let string = self.backend.resolve_unchecked(symbol);
+                index_nfc(nfc, &self.hasher, &self.backend, string.as_ref(), symbol)
+            };
+        }
+    }
+
     /// Interns the given string.
     ///
     /// Returns a symbol for resolution into the original string.
     ///
     /// # Panics
     ///
-    /// If the interner already interns the maximum number of strings possible
-    /// by the chosen symbol type.
+    /// - If the interner already interns the maximum number of strings possible
+    ///   by the chosen symbol type.
+    /// - If the string is longer than the
+    ///   [maximum string length][StringInterner::set_max_string_len].
     #[inline]
+    #[track_caller]
     pub fn get_or_intern<T>(&mut self, string: T) -> <B as Backend<'i>>::Symbol
     where
         T: AsRef<str>,
     {
-        self.get_or_intern_using(string.as_ref(), B::intern)
+        self.try_get_or_intern(string)
+            .expect("string exceeds the maximum string length")
     }
 
-    /// Interns the given `'static` string.
-    /// 
-    /// Returns a symbol for resolution into the original string.
-    /// 
-    /// If the backend supports [`'static` interning][crate::_docs::comparison_table],
+    /// Interns the given string and returns its symbol as a `usize` id.
+    ///
+    /// Use [`StringInterner::resolve_id`] to resolve the id back into the
+    /// string. This allows working with plain integer ids instead of symbols.
+    /// For backends whose symbols are consecutive indices, such as the
+    /// [`BucketBackend`] and the [`StringBackend`](crate::backend::StringBackend),
+    /// the ids are dense and suitable for indexing arrays.
+    ///
+    /// # Panics
+    ///
+    /// See [`StringInterner::get_or_intern`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::DefaultStringInterner;
+    /// let mut interner = <DefaultStringInterner>::new();
+    /// let a = interner.get_or_intern_id("a");
+    /// let b = interner.get_or_intern_id("b");
+    /// assert_eq!((a, b), (0, 1));
+    /// assert_eq!(interner.resolve_id(b), Some("b"));
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn get_or_intern_id<T>(&mut self, string: T) -> usize
+    where
+        T: AsRef<str>,
+    {
+        self.get_or_intern(string).to_usize()
+    }
+
+    /// Interns the given string if it does not exceed the
+    /// [maximum string length][StringInterner::set_max_string_len].
+    ///
+    /// Returns a symbol for resolution into the original string.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InternerError::CapacityExceeded`] if the string is too long.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    #[inline]
+    #[track_caller]
+    pub fn try_get_or_intern<T>(
+        &mut self,
+        string: T,
+    ) -> Result<<B as Backend<'i>>::Symbol, InternerError>
+    where
+        T: AsRef<str>,
+    {
+        let string = string.as_ref();
+        if let Some(max) = self.max_string_len.filter(|&max| string.len() > max) {
+            return Err(ExceedsCapacityError::new(string.len(), max).into());
+        }
+        // SAFETY: Interning a copy of the string has no safety requirements.
+        Ok(unsafe { self.get_or_intern_using(InternCopied, string) })
+    }
+
+    /// Interns the given UTF-8 encoded bytes.
+    ///
+    /// Returns a symbol for resolution into the interned string. Strings are
+    /// deduplicated by their bytes, so interning the same content as `str`
+    /// and as bytes yields the same symbol.
+    ///
+    /// # Errors
+    ///
+    /// If `bytes` are not valid UTF-8. Nothing is interned in that case.
+    ///
+    /// # Panics
+    ///
+    /// See [`StringInterner::get_or_intern`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::DefaultStringInterner;
+    /// let mut interner = <DefaultStringInterner>::new();
+    /// let foo = interner.get_or_intern("foo");
+    /// assert_eq!(interner.get_or_intern_bytes(b"foo"), Ok(foo));
+    /// assert_eq!(interner.resolve_bytes(foo), Some(&b"foo"[..]));
+    /// assert!(interner.get_or_intern_bytes(b"\xff").is_err());
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn get_or_intern_bytes(
+        &mut self,
+        bytes: &[u8],
+    ) -> Result<<B as Backend<'i>>::Symbol, Utf8Error> {
+        let string = core::str::from_utf8(bytes)?;
+        Ok(self.get_or_intern(string))
+    }
+
+    /// Interns the given `'static` string.
+    /// 
+    /// Returns a symbol for resolution into the original string.
+    /// 
+    /// If the backend supports [`'static` interning][crate::_docs::comparison_table],
     /// later calls to this or [`get_or_intern`][StringInterner::get_or_intern] function
     /// will return a symbol that resolves to the original `&'static str` reference.
     ///
-    /// # Note
+    /// # Note
+    ///
+    /// This is more efficient than [`StringInterner::get_or_intern`] since it might avoid
+    /// some memory allocations if the backends supports this.
+    ///
+    /// Additionally the interner remembers the address of every `'static` string
+    /// passed to this method. Interning the identical `&'static str` again, for
+    /// example the same string literal in a hot loop, then skips hashing its
+    /// contents. This only helps if the pointers are identical: equal contents at
+    /// different addresses still take the content hashing path.
+    ///
+    /// # Panics
+    ///
+    /// - If the interner already interns the maximum number of strings possible by the
+    ///   chosen symbol type.
+    /// - If the string is longer than the
+    ///   [maximum string length][StringInterner::set_max_string_len].
+    #[inline]
+    #[track_caller]
+    pub fn get_or_intern_static(&mut self, string: &'static str) -> <B as Backend<'i>>::Symbol {
+        assert!(
+            self.is_within_max_len(string),
+            "string exceeds the maximum string length"
+        );
+        if !self.deduplicate {
+            // SAFETY: Interning a `'static` string has no safety requirements.
+            return unsafe { self.get_or_intern_using(InternStatic, string) };
+        }
+        let key: StaticKey = (string.as_ptr() as usize, string.len());
+        let hash = make_hash(&self.hasher, &key);
+        if let Some(&(_, symbol)) = self.statics.find(hash, |(other, _)| *other == key) {
+            #[cfg(feature = "stats")]
+            self.stats.record(true);
+            return symbol;
+        }
+        // SAFETY: Interning a `'static` string has no safety requirements.
+        let symbol = unsafe { self.get_or_intern_using(InternStatic, string) };
+        let Self { statics, hasher, .. } = self;
+        statics.insert_unique(hash, (key, symbol), |(key, _)| make_hash(hasher, key));
+        symbol
+    }
+
+    /// Interns the given owned string by leaking it as a `'static` string.
+    ///
+    /// Returns the symbol of the already interned string if any and drops
+    /// `string` in that case. Otherwise `string` is leaked and interned via
+    /// [`StringInterner::get_or_intern_static`].
+    ///
+    /// # Note
+    ///
+    /// The memory of leaked strings is never freed, not even when the interner
+    /// is dropped. Use this only for strings that live for the rest of the
+    /// program and that should not be copied into the backend.
+    ///
+    /// # Panics
+    ///
+    /// See [`StringInterner::get_or_intern_static`].
+    #[inline]
+    #[track_caller]
+    pub fn get_or_intern_leaked(&mut self, string: String) -> <B as Backend<'i>>::Symbol {
+        assert!(
+            self.is_within_max_len(&string),
+            "string exceeds the maximum string length"
+        );
+        if self.deduplicate {
+            if let Some(symbol) = self.get(&string) {
+                #[cfg(feature = "stats")]
+                self.stats.record(true);
+                return symbol;
+            }
+        }
+        self.get_or_intern_static(Box::leak(string.into_boxed_str()))
+    }
+
+    /// Interns the given string unless a string equal to it ignoring ASCII case
+    /// has been interned already.
+    ///
+    /// Returns the symbol of the first-seen casing of the string. Consequently
+    /// [`resolve`][StringInterner::resolve] returns the casing as it was
+    /// originally interned and not the casing of `string`, while
+    /// [`canonical_key`][StringInterner::canonical_key] returns the case-folded
+    /// string used for the lookup.
+    ///
+    /// Only ASCII letters are folded, other characters must match exactly.
+    ///
+    /// # Note
+    ///
+    /// The first call indexes all strings interned so far by their case-folded
+    /// form. From then on every newly interned string is indexed as well which
+    /// costs an additional hash per string.
+    ///
+    /// # Panics
+    ///
+    /// See [`StringInterner::get_or_intern`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::DefaultStringInterner;
+    /// let mut interner = <DefaultStringInterner>::new();
+    /// let foo = interner.get_or_intern("FOO");
+    /// assert_eq!(interner.get_or_intern_ascii_ci("foo"), foo);
+    /// assert_eq!(interner.resolve(foo), Some("FOO"));
+    /// assert_eq!(interner.canonical_key(foo).as_deref(), Some("foo"));
+    /// ```
+    #[track_caller]
+    pub fn get_or_intern_ascii_ci<T>(&mut self, string: T) -> <B as Backend<'i>>::Symbol
+    where
+        T: AsRef<str>,
+    {
+        let string = string.as_ref();
+        assert!(
+            self.is_within_max_len(string),
+            "string exceeds the maximum string length"
+        );
+        let Self {
+            hasher,
+            backend,
+            folded,
+            ..
+        } = self;
+        let folded = folded.get_or_insert_with(|| {
+            let mut folded = HashTable::new();
+            for (symbol, other) in backend.iter() {
+                // SAFETY: This is safe because we only operate on symbols that
+                //         we receive from our backend making them valid.
+                unsafe { index_folded(&mut folded, hasher, backend, other.as_ref(), symbol) };
+            }
+            folded
+        });
+        let hash = make_folded_hash(hasher, string);
+        let existing = folded.find(hash, |symbol| {
+            // SAFETY: This is safe because we only operate on symbols that
+            //         we receive from our backend making them valid.
+            string.eq_ignore_ascii_case(unsafe { backend.resolve_unchecked(*symbol) }.as_ref())
+        });
+        match existing {
+            Some(&symbol) => {
+                #[cfg(feature = "stats")]
+                self.stats.record(true);
+                symbol
+            }
+            None => self.get_or_intern(string),
+        }
+    }
+
+    /// Interns the given string unless a canonically equivalent string has been
+    /// interned already.
+    ///
+    /// Strings are deduplicated by their Unicode Normalization Form C (NFC), so
+    /// that for example a precomposed `é` and an `e` followed by a combining
+    /// acute accent share a symbol. The string is stored as given and not in its
+    /// normalized form, so [`resolve`][StringInterner::resolve] returns the
+    /// first-seen form of the string. Strings already in NFC are not normalized.
+    ///
+    /// # Note
+    ///
+    /// The first call indexes all strings interned so far by their NFC form.
+    /// From then on every newly interned string is indexed as well which costs
+    /// an additional hash per string.
+    ///
+    /// # Panics
+    ///
+    /// See [`StringInterner::get_or_intern`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::DefaultStringInterner;
+    /// let mut interner = <DefaultStringInterner>::new();
+    /// let decomposed = interner.get_or_intern_nfc("cafe\u{301}");
+    /// assert_eq!(interner.get_or_intern_nfc("caf\u{e9}"), decomposed);
+    /// assert_eq!(interner.resolve(decomposed), Some("cafe\u{301}"));
+    /// ```
+    #[cfg(feature = "unicode")]
+    #[track_caller]
+    pub fn get_or_intern_nfc<T>(&mut self, string: T) -> <B as Backend<'i>>::Symbol
+    where
+        T: AsRef<str>,
+    {
+        let string = string.as_ref();
+        assert!(
+            self.is_within_max_len(string),
+            "string exceeds the maximum string length"
+        );
+        let Self {
+            hasher,
+            backend,
+            nfc,
+            ..
+        } = self;
+        let nfc = nfc.get_or_insert_with(|| {
+            let mut nfc = HashTable::new();
+            for (symbol, other) in backend.iter() {
+                // SAFETY: This is safe because we only operate on symbols that
+                //         we receive from our backend making them valid.
+                unsafe { index_nfc(&mut nfc, hasher, backend, other.as_ref(), symbol) };
+            }
+            nfc
+        });
+        let hash = make_nfc_hash(hasher, string);
+        let existing = nfc.find(hash, |symbol| {
+            // SAFETY: This is safe because we only operate on symbols that
+            //         we receive from our backend making them valid.
+            eq_nfc(string, unsafe { backend.resolve_unchecked(*symbol) }.as_ref())
+        });
+        match existing {
+            Some(&symbol) => {
+                #[cfg(feature = "stats")]
+                self.stats.record(true);
+                symbol
+            }
+            None => self.get_or_intern(string),
+        }
+    }
+
+    /// Interns the normalized form of the given string.
+    ///
+    /// Applies `normalize` to `string` and interns the result, so that all
+    /// inputs normalizing to the same string share a symbol which resolves to
+    /// the normalized string. Returning [`Cow::Borrowed`] from `normalize`
+    /// does not allocate.
+    ///
+    /// # Panics
+    ///
+    /// See [`StringInterner::get_or_intern`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::DefaultStringInterner;
+    /// # use std::borrow::Cow;
+    /// let mut interner = <DefaultStringInterner>::new();
+    /// let a = interner.get_or_intern_normalized("  name ", |s| Cow::Borrowed(s.trim()));
+    /// let b = interner.get_or_intern_normalized("name\n", |s| Cow::Borrowed(s.trim()));
+    /// assert_eq!(a, b);
+    /// assert_eq!(interner.resolve(a), Some("name"));
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn get_or_intern_normalized<F>(&mut self, string: &str, normalize: F) -> <B as Backend<'i>>::Symbol
+    where
+        F: FnOnce(&str) -> Cow<'_, str>,
+    {
+        let normalized = normalize(string);
+        self.get_or_intern(normalized.as_ref())
+    }
+
+    /// Returns the key used by [`get_or_intern_ascii_ci`][StringInterner::get_or_intern_ascii_ci]
+    /// to deduplicate the string for the given `symbol` if any.
+    ///
+    /// This is the resolved string with all ASCII letters folded to lowercase.
+    /// It is borrowed from the interner if the string contains no uppercase
+    /// ASCII letters. Use [`resolve`][StringInterner::resolve] to obtain the
+    /// string as it has been interned.
+    pub fn canonical_key<'l>(&'l self, symbol: <B as Backend<'i>>::Symbol) -> Option<Cow<'l, str>>
+    where
+        'i: 'l,
+        <B as Backend<'i>>::Access<'l>: Into<&'l str>,
+    {
+        let string: &'l str = self.resolve(symbol)?.into();
+        if string.bytes().any(|byte| byte.is_ascii_uppercase()) {
+            Some(Cow::Owned(string.to_ascii_lowercase()))
+        } else {
+            Some(Cow::Borrowed(string))
+        }
+    }
+
+    /// Converts the interner into one using the backend `B2`.
+    ///
+    /// The strings of the backend are re-interned into a fresh `B2` in symbol
+    /// order. The hasher, the [maximum string length](StringInterner::max_string_len)
+    /// and the [deduplication mode](StringInterner::deduplicates) are kept.
+    ///
+    /// # Note
+    ///
+    /// Symbols are only preserved if both backends assign symbols the same way.
+    /// This holds for the [`BucketBackend`] and the
+    /// [`StringBackend`](crate::backend::StringBackend) which both use consecutive
+    /// indices, but not for the [`BufferBackend`](crate::backend::BufferBackend)
+    /// whose symbols are byte offsets. In that case look up the new symbols with
+    /// [`StringInterner::get`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::{backend::{BucketBackend, StringBackend}, StringInterner};
+    /// let mut interner = StringInterner::<BucketBackend>::new();
+    /// let aa = interner.get_or_intern("aa");
+    /// let converted = interner.convert_backend::<StringBackend>();
+    /// assert_eq!(converted.resolve(aa), Some("aa"));
+    /// ```
+    #[track_caller]
+    pub fn convert_backend<B2>(self) -> StringInterner<'i, B2, H>
+    where
+        B2: Backend<'i, Symbol = <B as Backend<'i>>::Symbol>,
+    {
+        let len = self.len();
+        let mut backend = B2::with_capacity(len);
+        for (_, string) in self.backend.iter() {
+            let _ = backend.intern(string.as_ref());
+        }
+        let Self {
+            hasher,
+            max_string_len,
+            deduplicate,
+            ..
+        } = self;
+        let mut interner = StringInterner::from_parts(backend, hasher, len);
+        interner.max_string_len = max_string_len;
+        if !deduplicate {
+            interner.appended = interner.backend.iter().count();
+            interner.deduplicate = false;
+            interner.dedup = HashTable::new();
+        }
+        interner
+    }
+
+    /// Interns all strings of `other` into this interner.
+    ///
+    /// The strings of `other` are visited in its symbol order (via [`Backend::iter`])
+    /// and not in the order of its internal hash map. This makes merging deterministic:
+    /// strings not yet present in `self` are assigned new symbols in the same relative
+    /// order they have in `other`.
+    #[track_caller]
+    pub fn merge<'j, B2, H2>(&mut self, other: &StringInterner<'j, B2, H2>)
+    where
+        B2: Backend<'j>,
+        <B2 as Backend<'j>>::Symbol: Symbol,
+        H2: BuildHasher,
+    {
+        for (_symbol, string) in other.iter() {
+            self.get_or_intern(string.as_ref());
+        }
+    }
+
+    /// Interns all given strings and returns their symbols in input order.
+    ///
+    /// Reserves capacity for the lower bound of the iterator's size hint up front.
+    ///
+    /// # Panics
+    ///
+    /// See [`StringInterner::get_or_intern`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::StringInterner;
+    /// let mut interner = StringInterner::default();
+    /// let symbols = interner.intern_all(["a", "b", "a"]);
+    /// assert_eq!(symbols[0], symbols[2]);
+    /// assert_eq!(interner.resolve(symbols[1]), Some("b"));
+    /// ```
+    #[track_caller]
+    pub fn intern_all<T, I>(&mut self, strings: I) -> Vec<<B as Backend<'i>>::Symbol>
+    where
+        T: AsRef<str>,
+        I: IntoIterator<Item = T>,
+    {
+        let mut symbols = Vec::new();
+        self.intern_all_into(strings, &mut symbols);
+        symbols
+    }
+
+    /// Interns all given strings and appends their symbols to `symbols` in input order.
+    ///
+    /// Like [`StringInterner::intern_all`] but reuses the given buffer, which
+    /// avoids allocating a new one per batch.
+    ///
+    /// # Panics
+    ///
+    /// See [`StringInterner::get_or_intern`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::StringInterner;
+    /// let mut interner = StringInterner::default();
+    /// let mut symbols = Vec::new();
+    /// interner.intern_all_into(["a", "b"], &mut symbols);
+    /// interner.intern_all_into(["a"], &mut symbols);
+    /// assert_eq!(symbols.len(), 3);
+    /// assert_eq!(symbols[0], symbols[2]);
+    /// ```
+    #[track_caller]
+    pub fn intern_all_into<T, I>(
+        &mut self,
+        strings: I,
+        symbols: &mut Vec<<B as Backend<'i>>::Symbol>,
+    ) where
+        T: AsRef<str>,
+        I: IntoIterator<Item = T>,
+    {
+        let strings = strings.into_iter();
+        let (additional, _) = strings.size_hint();
+        self.reserve(additional);
+        symbols.reserve(additional);
+        // A plain loop keeps the caller location of panics, unlike a closure.
+        for string in strings {
+            symbols.push(self.get_or_intern(string));
+        }
+    }
+
+    /// Interns all given strings and returns their symbols in input order,
+    /// short-circuiting repeats of the two most recently interned strings.
+    ///
+    /// Strings equal to one of the two previous distinct strings of the batch
+    /// reuse its symbol without hashing, which speeds up interning batches with
+    /// long runs of duplicates, for example sorted categorical data. Unlike
+    /// [`StringInterner::intern_all`] this reserves no capacity up front since
+    /// the number of distinct strings is unknown.
+    ///
+    /// # Note
+    ///
+    /// Interners created by [`StringInterner::new_nodedup`] intern every string
+    /// of the batch, the same as [`StringInterner::intern_all`].
+    ///
+    /// # Panics
+    ///
+    /// See [`StringInterner::get_or_intern`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::StringInterner;
+    /// let mut interner = StringInterner::default();
+    /// let symbols = interner.intern_dedup_batch(["a", "a", "b", "a", "c"]);
+    /// assert_eq!(symbols[0], symbols[1]);
+    /// assert_eq!(symbols[0], symbols[3]);
+    /// assert_eq!(interner.len(), 3);
+    /// ```
+    #[track_caller]
+    pub fn intern_dedup_batch<'a, I>(&mut self, strings: I) -> Vec<<B as Backend<'i>>::Symbol>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut symbols = Vec::new();
+        if !self.deduplicate {
+            // A plain loop keeps the caller location of panics, unlike a closure.
+            for string in strings {
+                symbols.push(self.get_or_intern(string));
+            }
+            return symbols;
+        }
+        let mut recent: [Option<(&'a str, <B as Backend<'i>>::Symbol)>; 2] = [None, None];
+        for string in strings {
+            let cached = recent
+                .iter()
+                .flatten()
+                .find(|(other, _)| *other == string)
+                .map(|&(_, symbol)| symbol);
+            let symbol = match cached {
+                Some(symbol) => {
+                    #[cfg(feature = "stats")]
+                    self.stats.record(true);
+                    symbol
+                }
+                None => {
+                    let symbol = self.get_or_intern(string);
+                    recent = [Some((string, symbol)), recent[0]];
+                    symbol
+                }
+            };
+            symbols.push(symbol);
+        }
+        symbols
+    }
+
+    /// Interns all whitespace separated tokens of `text` and returns their
+    /// symbols in order.
+    ///
+    /// Tokens are split by [`str::split_whitespace`], so empty tokens are
+    /// skipped. The tokens are counted up front to reserve capacity for them.
+    ///
+    /// # Panics
+    ///
+    /// See [`StringInterner::get_or_intern`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::StringInterner;
+    /// let mut interner = StringInterner::default();
+    /// let symbols = interner.intern_whitespace_split(" let x =\tx\n");
+    /// assert_eq!(symbols.len(), 4);
+    /// assert_eq!(symbols[1], symbols[3]);
+    /// assert_eq!(interner.resolve(symbols[2]), Some("="));
+    /// ```
+    #[track_caller]
+    pub fn intern_whitespace_split(&mut self, text: &str) -> Vec<<B as Backend<'i>>::Symbol> {
+        let tokens = text.split_whitespace();
+        let additional = tokens.clone().count();
+        self.reserve(additional);
+        let mut symbols = Vec::with_capacity(additional);
+        for token in tokens {
+            symbols.push(self.get_or_intern(token));
+        }
+        symbols
+    }
+
+    /// Interns every line read from `reader` and returns their symbols in order.
+    ///
+    /// Lines are split like [`BufRead::lines`](std::io::BufRead::lines), so the
+    /// trailing `\n` or `\r\n` is not part of the interned string. A single
+    /// line buffer is reused for all lines.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of `reader`, including [`InvalidData`] errors
+    /// for lines that are not valid UTF-8. The lines read up to the error
+    /// remain interned.
+    ///
+    /// [`InvalidData`]: std::io::ErrorKind::InvalidData
+    ///
+    /// # Panics
+    ///
+    /// See [`StringInterner::get_or_intern`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::StringInterner;
+    /// let mut interner = StringInterner::default();
+    /// let symbols = interner.intern_lines("aa\nbb\r\naa".as_bytes()).unwrap();
+    /// assert_eq!(symbols.len(), 3);
+    /// assert_eq!(symbols[0], symbols[2]);
+    /// assert_eq!(interner.resolve(symbols[1]), Some("bb"));
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    pub fn intern_lines<R>(&mut self, mut reader: R) -> std::io::Result<Vec<<B as Backend<'i>>::Symbol>>
+    where
+        R: std::io::BufRead,
+    {
+        let mut symbols = Vec::new();
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Ok(symbols);
+            }
+            let trimmed = match line.strip_suffix('\n') {
+                Some(line) => line.strip_suffix('\r').unwrap_or(line),
+                None => &line,
+            };
+            symbols.push(self.get_or_intern(trimmed));
+        }
+    }
+
+    /// Reserves capacity for at least `additional` more strings to be interned.
+    ///
+    /// This reserves space in both the internal hash map used for deduplication
+    /// and the backend, avoiding repeated regrowth when interning many strings.
+    /// The hash map accounts for its load factor so that interning `additional`
+    /// new strings does not resize it.
+    pub fn reserve(&mut self, additional: usize) {
+        let Self {
+            dedup,
+            hasher,
+            backend,
+            deduplicate,
+            ..
+        } = self;
+        if *deduplicate {
+            dedup.reserve(additional, |symbol| {
+                // SAFETY: This is safe because we only operate on symbols that
+                //         we receive from our backend making them valid.
+                unsafe { make_symbol_hash(hasher, backend, *symbol) }
+            });
+        }
+        backend.reserve(additional);
+    }
+
+    /// Reserves capacity for exactly `additional` more strings of `bytes` bytes in total.
+    ///
+    /// Unlike [`StringInterner::reserve`] the backend does not over-allocate,
+    /// see [`Backend::reserve_exact`]. The hash map used for deduplication is
+    /// sized to the smallest capacity able to hold `additional` more strings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::{backend::StringBackend, StringInterner};
+    /// let mut interner = StringInterner::<StringBackend>::new();
+    /// interner.reserve_exact(3, 6);
+    /// for string in ["aa", "bb", "cc"] {
+    ///     interner.get_or_intern(string);
+    /// }
+    /// assert_eq!(interner.capacity_report().bytes_allocated, 6);
+    /// ```
+    pub fn reserve_exact(&mut self, additional: usize, bytes: usize) {
+        let Self {
+            dedup,
+            hasher,
+            backend,
+            deduplicate,
+            ..
+        } = self;
+        if *deduplicate {
+            dedup.reserve(additional, |symbol| {
+                // SAFETY: This is safe because we only operate on symbols that
+                //         we receive from our backend making them valid.
+                unsafe { make_symbol_hash(hasher, backend, *symbol) }
+            });
+        }
+        backend.reserve_exact(additional, bytes);
+    }
+
+    /// Returns a snapshot of the size and capacity of the interner.
+    ///
+    /// Gathers all metrics in a single call, see [`CapacityReport`] for details.
+    pub fn capacity_report(&self) -> CapacityReport {
+        CapacityReport {
+            strings: self.len(),
+            map_capacity: self.dedup.capacity(),
+            ..self.backend.capacity_report()
+        }
+    }
+
+    /// Shrink the capacity of the interner to fit the interned strings exactly.
+    ///
+    /// This shrinks both the internal hash map used for deduplication and the
+    /// backend, as well as the per-string tables of the `generations` and
+    /// `store-hashes` crate features. Use [`shrink_hashmap_to_fit`][StringInterner::shrink_hashmap_to_fit]
+    /// to only shrink the hash map.
+    pub fn shrink_to_fit(&mut self) {
+        self.shrink_hashmap_to_fit();
+        #[cfg(feature = "generations")]
+        self.generations.shrink_to_fit();
+        #[cfg(feature = "store-hashes")]
+        self.hashes.shrink_to_fit();
+        self.backend.shrink_to_fit()
+    }
+
+    /// Shrink the capacity of the internal hash map used for deduplication to
+    /// fit the interned strings without touching the backend.
+    ///
+    /// # Note
+    ///
+    /// This rehashes all interned strings which can be expensive for large interners.
+    pub fn shrink_hashmap_to_fit(&mut self) {
+        let Self {
+            dedup,
+            hasher,
+            backend,
+            ..
+        } = self;
+        dedup.shrink_to_fit(|symbol| {
+            // SAFETY: This is safe because we only operate on symbols that
+            //         we receive from our backend making them valid.
+            unsafe { make_symbol_hash(hasher, backend, *symbol) }
+        });
+    }
+
+    /// Returns the string for the given `symbol`` if any.
+    #[inline]
+    pub fn resolve(&self, symbol: <B as Backend<'i>>::Symbol) -> Option<<B as Backend<'i>>::Access<'_>> {
+        self.backend.resolve(symbol)
+    }
+
+    /// Returns `true` if the given `symbol` currently resolves to a string.
+    ///
+    /// Symbols become invalid by [`StringInterner::rollback`] and, for the
+    /// former last symbol, by [`StringInterner::swap_remove`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::{backend::BucketBackend, StringInterner};
+    /// let mut interner = StringInterner::<BucketBackend>::new();
+    /// let aa = interner.get_or_intern("aa");
+    /// let bb = interner.get_or_intern("bb");
+    /// interner.swap_remove(aa);
+    /// assert!(interner.is_valid(aa));
+    /// assert!(!interner.is_valid(bb));
+    /// ```
+    #[inline]
+    pub fn is_valid(&self, symbol: <B as Backend<'i>>::Symbol) -> bool {
+        self.resolve(symbol).is_some()
+    }
+
+    /// Returns a reference to the hasher of the interner.
+    #[inline]
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Returns a reference to the backend of the interner.
+    ///
+    /// Allows using backend specific APIs for introspection, for example
+    /// [`BucketBackend::buckets`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::{backend::BucketBackend, StringInterner};
+    /// let mut interner = StringInterner::<BucketBackend>::new();
+    /// interner.get_or_intern("aa");
+    /// let bytes = interner.backend().buckets().map(str::len).sum::<usize>();
+    /// assert_eq!(bytes, 2);
+    /// ```
+    #[inline]
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Returns an exclusive reference to the backend of the interner.
+    ///
+    /// Allows using backend specific APIs that do not change the interned
+    /// strings, for example [`Backend::shrink_to_fit`] or
+    /// [`BucketBackend::set_fixed_bucket_size`].
+    ///
+    /// # Safety
+    ///
+    /// The interner indexes the strings of the backend by their symbols. The
+    /// caller must not add, remove or modify strings through the returned
+    /// reference, nor change the symbols they resolve with, since the index
+    /// would then refer to strings that no longer exist. Operations that keep
+    /// every symbol resolving to the same string are fine.
+    #[inline]
+    pub unsafe fn backend_mut(&mut self) -> &mut B {
+        &mut self.backend
+    }
+
+    /// Returns the hash of the string for the given `symbol` if any.
+    ///
+    /// The hash is computed by the hasher of the interner when the string is
+    /// interned and equals [`BuildHasher::hash_one`] of the string. Reuse it to
+    /// avoid hashing resolved strings again, for example for partitioning.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::DefaultStringInterner;
+    /// # use core::hash::BuildHasher;
+    /// let mut interner = <DefaultStringInterner>::new();
+    /// let a = interner.get_or_intern("a");
+    /// assert_eq!(interner.hash_of(a), Some(interner.hasher().hash_one("a")));
+    /// ```
+    #[cfg(feature = "store-hashes")]
+    #[inline]
+    pub fn hash_of(&self, symbol: <B as Backend<'i>>::Symbol) -> Option<u64> {
+        self.hashes.get(symbol).copied()
+    }
+
+    /// Returns the string for the given `symbol` together with its hash if any.
+    ///
+    /// See [`StringInterner::hash_of`].
+    #[cfg(feature = "store-hashes")]
+    #[inline]
+    pub fn resolve_with_hash(
+        &self,
+        symbol: <B as Backend<'i>>::Symbol,
+    ) -> Option<(<B as Backend<'i>>::Access<'_>, u64)> {
+        Some((self.resolve(symbol)?, self.hash_of(symbol)?))
+    }
+
+    /// Returns the string for the given id if any.
+    ///
+    /// See [`StringInterner::get_or_intern_id`].
+    #[inline]
+    pub fn resolve_id(&self, id: usize) -> Option<<B as Backend<'i>>::Access<'_>> {
+        self.resolve(<B as Backend<'i>>::Symbol::try_from_usize(id)?)
+    }
+
+    /// Returns the string for the given `symbol`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidSymbolError`] carrying the symbol's index and the
+    /// number of interned strings if the symbol does not refer to a string.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::{DefaultStringInterner, InvalidSymbolError};
+    /// fn name<'a>(interner: &'a DefaultStringInterner) -> Result<&'a str, InvalidSymbolError> {
+    ///     let symbol = interner.get("name").expect("name is interned");
+    ///     interner.try_resolve(symbol)
+    /// }
+    /// let mut interner = <DefaultStringInterner>::new();
+    /// interner.get_or_intern("name");
+    /// assert_eq!(name(&interner), Ok("name"));
+    /// ```
+    #[inline]
+    pub fn try_resolve(
+        &self,
+        symbol: <B as Backend<'i>>::Symbol,
+    ) -> Result<<B as Backend<'i>>::Access<'_>, InvalidSymbolError> {
+        self.resolve(symbol)
+            .ok_or_else(|| InvalidSymbolError::new(symbol.to_usize(), self.len()))
+    }
+
+    /// Returns a copy of the string for the given `symbol` as [`Arc<str>`] if any.
+    ///
+    /// The returned string is independent of the interner and can be handed
+    /// to other threads that outlive the borrow of the interner. Every call
+    /// allocates, use [`resolve_shared_cached`][StringInterner::resolve_shared_cached]
+    /// to share a single allocation between calls.
+    #[inline]
+    pub fn resolve_shared(&self, symbol: <B as Backend<'i>>::Symbol) -> Option<Arc<str>> {
+        self.resolve(symbol).map(|string| Arc::from(string.as_ref()))
+    }
+
+    /// Returns the string for the given `symbol` as [`Arc<str>`] if any, reusing
+    /// the allocation stored for it in `arc_cache`.
+    ///
+    /// The string is copied into a new [`Arc<str>`] on the first call for a
+    /// symbol and stored in `arc_cache`. Later calls return clones of the same
+    /// allocation. Keeping the cache outside of the interner allows resolving
+    /// through a shared reference to the interner.
+    ///
+    /// # Note
+    ///
+    /// The cache must only be used with this interner. Symbols invalidated by
+    /// a rollback must be removed from the cache by the caller.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::{DefaultStringInterner, SymbolMap};
+    /// # use std::sync::Arc;
+    /// let mut interner = <DefaultStringInterner>::new();
+    /// let aa = interner.get_or_intern("aa");
+    /// let mut arc_cache = SymbolMap::new();
+    /// let a = interner.resolve_shared_cached(aa, &mut arc_cache).unwrap();
+    /// let b = interner.resolve_shared_cached(aa, &mut arc_cache).unwrap();
+    /// assert!(Arc::ptr_eq(&a, &b));
+    /// ```
+    pub fn resolve_shared_cached(
+        &self,
+        symbol: <B as Backend<'i>>::Symbol,
+        arc_cache: &mut SymbolMap<<B as Backend<'i>>::Symbol, Arc<str>>,
+    ) -> Option<Arc<str>> {
+        if let Some(shared) = arc_cache.get(symbol) {
+            return Some(Arc::clone(shared));
+        }
+        let shared = self.resolve_shared(symbol)?;
+        arc_cache.insert(symbol, Arc::clone(&shared));
+        Some(shared)
+    }
+
+    /// Returns the bytes of the string for the given `symbol` if any.
+    ///
+    /// See [`Backend::resolve_bytes`].
+    #[inline]
+    pub fn resolve_bytes<'l>(&'l self, symbol: <B as Backend<'i>>::Symbol) -> Option<&'l [u8]>
+    where
+        'i: 'l,
+        <B as Backend<'i>>::Access<'l>: Into<&'l str>,
+    {
+        self.backend.resolve_bytes(symbol)
+    }
+
+    /// Returns the string for the given `symbol` as a [`Cow`] if any.
+    ///
+    /// See [`Backend::resolve_cow`].
+    #[inline]
+    pub fn resolve_cow<'l>(&'l self, symbol: <B as Backend<'i>>::Symbol) -> Option<Cow<'l, str>>
+    where
+        'i: 'l,
+        <B as Backend<'i>>::Access<'l>: Into<Cow<'l, str>>,
+    {
+        self.backend.resolve_cow(symbol)
+    }
+
+    /// Returns the string for the given `symbol` if any, copying it unless the
+    /// backend keeps its strings in place.
+    ///
+    /// See [`Backend::resolve_owned_if_volatile`].
+    #[inline]
+    pub fn resolve_owned_if_volatile<'l>(
+        &'l self,
+        symbol: <B as Backend<'i>>::Symbol,
+    ) -> Option<Cow<'l, str>>
+    where
+        'i: 'l,
+        <B as Backend<'i>>::Access<'l>: Into<Cow<'l, str>>,
+    {
+        self.backend.resolve_owned_if_volatile(symbol)
+    }
+
+    /// Returns the strings for all of the given `symbols` if any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::StringInterner;
+    /// let mut interner = StringInterner::default();
+    /// let x = interner.get_or_intern("x");
+    /// let y = interner.get_or_intern("y");
+    /// let [a, b] = interner.resolve_array([x, y]);
+    /// assert_eq!((a, b), (Some("x"), Some("y")));
+    /// ```
+    #[inline]
+    pub fn resolve_array<const N: usize>(
+        &self,
+        symbols: [<B as Backend<'i>>::Symbol; N],
+    ) -> [Option<<B as Backend<'i>>::Access<'_>>; N] {
+        symbols.map(|symbol| self.resolve(symbol))
+    }
+
+    /// Returns the strings of all symbols in ascending symbol order.
+    ///
+    /// For backends whose symbols are consecutive indices, such as the
+    /// [`BucketBackend`] and the [`StringBackend`](crate::backend::StringBackend),
+    /// the returned [`Vec`] is indexed by [`Symbol::to_usize`]. This allows
+    /// resolving many symbols without calling [`StringInterner::resolve`] for
+    /// each of them. The [`BufferBackend`](crate::backend::BufferBackend) uses
+    /// byte offsets as symbols instead.
+    ///
+    /// The returned [`Vec`] borrows the interner for as long as it is alive, so
+    /// no strings can be interned in the meantime.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::{StringInterner, Symbol};
+    /// let mut interner = StringInterner::default();
+    /// let x = interner.get_or_intern("x");
+    /// let y = interner.get_or_intern("y");
+    /// let strings = interner.resolve_all();
+    /// assert_eq!(strings[y.to_usize()], "y");
+    /// assert_eq!(strings, ["x", "y"]);
+    /// ```
+    #[inline]
+    pub fn resolve_all(&self) -> Vec<<B as Backend<'i>>::Access<'_>> {
+        self.iter().map(|(_, string)| string).collect()
+    }
+
+    /// Returns the string for the given `symbol` wrapped in a [`Resolved`] handle if any.
+    ///
+    /// The handle can be compared directly against `str` and [`String`] values and
+    /// implements [`Display`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::StringInterner;
+    /// let mut interner = StringInterner::default();
+    /// let sym = interner.get_or_intern("Banana");
+    /// assert!(interner.resolved(sym).unwrap() == "Banana");
+    /// ```
+    #[inline]
+    pub fn resolved(
+        &self,
+        symbol: <B as Backend<'i>>::Symbol,
+    ) -> Option<Resolved<<B as Backend<'i>>::Access<'_>>> {
+        self.resolve(symbol).map(Resolved)
+    }
+
+    /// Returns a lazy formatter that displays the string of the given `symbol`.
+    ///
+    /// The symbol is resolved only when the formatter is used and nothing is
+    /// allocated. Invalid symbols are displayed as `<invalid:{index}>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::{StringInterner, Symbol, DefaultSymbol};
+    /// let mut interner = StringInterner::default();
+    /// let sym = interner.get_or_intern("Banana");
+    /// assert_eq!(format!("{}", interner.display(sym)), "Banana");
+    /// let invalid = DefaultSymbol::try_from_usize(42).unwrap();
+    /// assert_eq!(format!("{}", interner.display(invalid)), "<invalid:42>");
+    /// ```
+    #[inline]
+    pub fn display(&self, symbol: <B as Backend<'i>>::Symbol) -> DisplaySymbol<'_, 'i, B, H> {
+        DisplaySymbol {
+            interner: self,
+            symbol,
+        }
+    }
+
+    /// Returns the string for the given `symbol` without performing any checks.
+    ///
+    /// # Safety
+    ///
+    /// It is the caller's responsibility to provide this method with `symbol`s
+    /// that are valid for the [`StringInterner`].
+    #[inline]
+    pub unsafe fn resolve_unchecked(&self, symbol: <B as Backend<'i>>::Symbol) -> <B as Backend<'i>>::Access<'_> {
+        unsafe { self.backend.resolve_unchecked(symbol) }
+    }
+
+    /// Returns the first symbol in iteration order if any.
+    ///
+    /// For the backends provided by this crate this is the symbol of the
+    /// string that was interned first.
+    #[inline]
+    pub fn first_symbol(&self) -> Option<<B as Backend<'i>>::Symbol> {
+        self.iter().next().map(|(symbol, _)| symbol)
+    }
+
+    /// Returns the last symbol in iteration order if any.
+    ///
+    /// For the backends provided by this crate this is the symbol of the
+    /// string that was interned last.
+    ///
+    /// # Note
+    ///
+    /// This is a constant time operation for backends whose iterator implements
+    /// [`Iterator::last`] efficiently such as the
+    /// [`BucketBackend`] and
+    /// [`StringBackend`](crate::backend::StringBackend). Otherwise all
+    /// interned strings are visited.
+    #[inline]
+    pub fn last_symbol(&self) -> Option<<B as Backend<'i>>::Symbol> {
+        self.iter().last().map(|(symbol, _)| symbol)
+    }
+
+    /// Returns the number of bits needed to represent the largest symbol of the interner.
+    ///
+    /// Returns `0` if the interner is empty. Use this to decide on the narrowest
+    /// symbol type when converting or serializing the interner, see also
+    /// [`StringInterner::fits_in`].
+    ///
+    /// # Note
+    ///
+    /// This is based on the `usize` representation of the
+    /// [last symbol](StringInterner::last_symbol) and not on [`StringInterner::len`]
+    /// since the symbols of some backends, such as the
+    /// [`BufferBackend`](crate::backend::BufferBackend), are not consecutive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::DefaultStringInterner;
+    /// let mut interner = <DefaultStringInterner>::new();
+    /// assert_eq!(interner.required_symbol_bits(), 0);
+    /// for i in 0..300 {
+    ///     interner.get_or_intern(i.to_string());
+    /// }
+    /// assert_eq!(interner.required_symbol_bits(), 9);
+    /// ```
+    #[inline]
+    pub fn required_symbol_bits(&self) -> u32 {
+        self.last_symbol()
+            .map_or(0, |symbol| usize::BITS - symbol.to_usize().leading_zeros())
+    }
+
+    /// Returns `true` if all symbols of the interner can be represented by `S2`.
+    ///
+    /// See [`StringInterner::required_symbol_bits`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::{symbol::SymbolU16, DefaultStringInterner};
+    /// let mut interner = <DefaultStringInterner>::new();
+    /// interner.get_or_intern("a");
+    /// assert!(interner.fits_in::<SymbolU16>());
+    /// ```
+    #[inline]
+    pub fn fits_in<S2>(&self) -> bool
+    where
+        S2: Symbol,
+    {
+        self.last_symbol()
+            .is_none_or(|symbol| symbol.to_usize() <= S2::MAX_INDEX)
+    }
+
+    /// Converts the interner to use the symbol type `S2`.
+    ///
+    /// The interned strings are kept in place and every symbol keeps its `usize`
+    /// representation, so symbols can be converted with [`Symbol::to_usize`] and
+    /// [`Symbol::try_from_usize`]. Only the hash maps of the interner are rebuilt.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OutOfBoundsError`] if `S2` cannot represent the symbols of all
+    /// interned strings, see [`StringInterner::fits_in`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::{backend::StringBackend, symbol::{SymbolU16, SymbolU32}, Symbol, StringInterner};
+    /// let mut interner = StringInterner::<StringBackend<SymbolU16>>::new();
+    /// let narrow = interner.get_or_intern("aa");
+    /// let mut interner = interner.with_symbol_type::<SymbolU32>().unwrap();
+    /// let wide = SymbolU32::try_from_usize(narrow.to_usize()).unwrap();
+    /// assert_eq!(interner.get("aa"), Some(wide));
+    /// assert_eq!(interner.resolve(wide), Some("aa"));
+    /// ```
+    pub fn with_symbol_type<S2>(
+        self,
+    ) -> Result<StringInterner<'i, <B as RebindSymbol<'i, S2>>::Rebound, H>, OutOfBoundsError>
+    where
+        B: RebindSymbol<'i, S2>,
+        S2: Symbol,
+    {
+        if let Some(last) = self.last_symbol().map(Symbol::to_usize) {
+            if last > S2::MAX_INDEX {
+                return Err(OutOfBoundsError::new(last, S2::MAX_INDEX));
+            }
+        }
+        let rebind = |symbol: <B as Backend<'i>>::Symbol| {
+            S2::try_from_usize(symbol.to_usize()).expect("encountered invalid symbol")
+        };
+        let Self {
+            dedup: old_dedup,
+            statics: old_statics,
+            hasher,
+            backend,
+            max_string_len,
+            deduplicate,
+            appended,
+            removed,
+            #[cfg(feature = "generations")]
+            generation,
+            #[cfg(feature = "generations")]
+            generations,
+            #[cfg(feature = "store-hashes")]
+            hashes,
+            #[cfg(feature = "stats")]
+            stats,
+            ..
+        } = self;
+        let backend = backend.rebind_symbol();
+        let symbol_hash = |symbol: &S2| {
+            // SAFETY: The symbols have been created by the backend before it
+            //         was rebound and keep their `usize` representation.
+            unsafe { make_symbol_hash(&hasher, &backend, *symbol) }
+        };
+        let mut dedup = HashTable::with_capacity(old_dedup.len());
+        for symbol in old_dedup {
+            let symbol = rebind(symbol);
+            dedup.insert_unique(symbol_hash(&symbol), symbol, symbol_hash);
+        }
+        let mut statics = HashTable::with_capacity(old_statics.len());
+        for (key, symbol) in old_statics {
+            statics.insert_unique(make_hash(&hasher, &key), (key, rebind(symbol)), |(key, _)| {
+                make_hash(&hasher, key)
+            });
+        }
+        Ok(StringInterner {
+            dedup,
+            statics,
+            hasher,
+            backend,
+            max_string_len,
+            deduplicate,
+            appended,
+            removed,
+            // The case-folded and NFC indices are rebuilt by the next lookup.
+            folded: None,
+            #[cfg(feature = "unicode")]
+            nfc: None,
+            #[cfg(feature = "small-string-cache")]
+            small: SmallStrings::default(),
+            #[cfg(feature = "generations")]
+            generation,
+            #[cfg(feature = "generations")]
+            generations: generations.rebind(),
+            #[cfg(feature = "store-hashes")]
+            hashes: hashes.rebind(),
+            #[cfg(feature = "stats")]
+            stats,
+        })
+    }
+
+    /// Returns an iterator that yields all interned strings and their symbols.
+    ///
+    /// The strings are yielded in ascending symbol order. If the iterator of
+    /// the backend implements [`DoubleEndedIterator`], as for the [`BucketBackend`]
+    /// and the [`StringBackend`](crate::backend::StringBackend), `iter().rev()`
+    /// yields the most recently interned strings first in descending symbol order.
+    ///
+    /// Only valid symbols are yielded. Removing strings never leaves vacant
+    /// symbols behind since [`StringInterner::swap_remove`] moves the last
+    /// string into the place of the removed one.
+    #[inline]
+    pub fn iter(&self) -> <B as Backend<'i>>::Iter<'_> {
+        self.backend.iter()
+    }
+
+    /// Returns an iterator that yields all interned strings in the order they
+    /// have been interned first, or `None` if the backend does not guarantee it.
+    ///
+    /// Relies on [`Backend::SYMBOLS_MONOTONIC`] so that no sorting is needed,
+    /// which is the case for all backends of this crate. Returns `None` once a
+    /// string has been removed by [`StringInterner::swap_remove`] since the
+    /// moved string takes the place of the removed one.
     ///
-    /// This is more efficient than [`StringInterner::get_or_intern`] since it might avoid
-    /// some memory allocations if the backends supports this.
+    /// # Example
     ///
-    /// # Panics
+    /// ```
+    /// # use string_interner::DefaultStringInterner;
+    /// let mut interner = <DefaultStringInterner>::new();
+    /// let b = interner.get_or_intern("b");
+    /// let a = interner.get_or_intern("a");
+    /// interner.get_or_intern("b");
+    /// assert!(b < a);
+    /// let strings = interner.iter_insertion_order().unwrap().map(|(_, s)| s);
+    /// assert!(strings.eq(["b", "a"]));
+    /// ```
+    #[inline]
+    pub fn iter_insertion_order(&self) -> Option<<B as Backend<'i>>::Iter<'_>> {
+        (B::SYMBOLS_MONOTONIC && !self.removed).then(|| self.backend.iter())
+    }
+
+    /// Returns a snapshot of the number of interned strings.
     ///
-    /// If the interner already interns the maximum number of strings possible by the
-    /// chosen symbol type.
+    /// Use [`StringInterner::since`] to iterate over the strings interned after it.
     #[inline]
-    pub fn get_or_intern_static(&mut self, string: &'static str) -> <B as Backend<'i>>::Symbol {
-        self.get_or_intern_using(string, B::intern_static)
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot { len: self.len() }
     }
 
-    /// Shrink backend capacity to fit the interned strings exactly.
-    pub fn shrink_to_fit(&mut self) {
-        self.backend.shrink_to_fit()
+    /// Returns an iterator over the strings interned since the given snapshot.
+    ///
+    /// The strings are yielded in ascending symbol order like for
+    /// [`StringInterner::iter`].
+    ///
+    /// # Note
+    ///
+    /// The backends of this crate only ever append strings so these are exactly
+    /// the strings interned after the snapshot was taken, given that no strings
+    /// have been removed in the meantime.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::DefaultStringInterner;
+    /// let mut interner = <DefaultStringInterner>::new();
+    /// interner.get_or_intern("aa");
+    /// let snapshot = interner.snapshot();
+    /// let bb = interner.get_or_intern("bb");
+    /// interner.get_or_intern("aa");
+    /// assert_eq!(interner.since(&snapshot).collect::<Vec<_>>(), [(bb, "bb")]);
+    /// ```
+    #[inline]
+    pub fn since(&self, snapshot: &Snapshot) -> Skip<<B as Backend<'i>>::Iter<'_>> {
+        self.backend.iter().skip(snapshot.len)
     }
+}
 
-    /// Returns the string for the given `symbol`` if any.
+#[cfg(feature = "backends")]
+impl<'i, S, H> StringInterner<'i, BucketBackend<'i, S>, H>
+where
+    S: Symbol,
+    H: BuildHasher,
+{
+    /// Returns a checkpoint of the current state of the interner.
+    ///
+    /// Use [`StringInterner::rollback`] to discard all strings interned after it.
     #[inline]
-    pub fn resolve(&self, symbol: <B as Backend<'i>>::Symbol) -> Option<<B as Backend<'i>>::Access<'_>> {
-        self.backend.resolve(symbol)
+    pub fn checkpoint(&self) -> Checkpoint {
+        self.backend.checkpoint()
     }
 
-    /// Returns the string for the given `symbol` without performing any checks.
+    /// Discards all strings interned since the given checkpoint.
     ///
     /// # Safety
     ///
-    /// It is the caller's responsibility to provide this method with `symbol`s
-    /// that are valid for the [`StringInterner`].
+    /// - `checkpoint` must have been created by [`StringInterner::checkpoint`] of
+    ///   this interner.
+    /// - The interner must not have been rolled back to an earlier checkpoint
+    ///   in the meantime.
+    ///
+    /// All symbols created after the checkpoint become invalid and must no
+    /// longer be used.
+    pub unsafe fn rollback(&mut self, checkpoint: Checkpoint) {
+        let len = checkpoint.len();
+        self.dedup.retain(|symbol| symbol.to_usize() < len);
+        self.statics.retain(|(_, symbol)| symbol.to_usize() < len);
+        if let Some(folded) = &mut self.folded {
+            folded.retain(|symbol| symbol.to_usize() < len);
+        }
+        #[cfg(feature = "unicode")]
+        if let Some(nfc) = &mut self.nfc {
+            nfc.retain(|symbol| symbol.to_usize() < len);
+        }
+        if !self.deduplicate {
+            self.appended = len;
+        }
+        #[cfg(feature = "generations")]
+        self.generations.truncate(len);
+        #[cfg(feature = "store-hashes")]
+        self.hashes.truncate(len);
+        #[cfg(feature = "small-string-cache")]
+        self.small.retain(|symbol| symbol.to_usize() < len);
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        unsafe { self.backend.rollback(checkpoint) }
+    }
+
+    /// Moves all interned strings into a single bucket that fits them exactly.
+    ///
+    /// Use this before freezing or sharing an interner that is not going to
+    /// change anymore. Symbols remain valid.
+    ///
+    /// # Note
+    ///
+    /// This copies all interned strings once and does not shrink the hash map,
+    /// combine it with [`StringInterner::shrink_to_fit`] for that. See
+    /// [`BucketBackend::compact_storage`] for details.
+    pub fn compact_storage(&mut self) {
+        self.backend.compact_storage()
+    }
+
+    /// Removes the string of `symbol` and moves the string of the last symbol into its place.
+    ///
+    /// Returns the removed string and the symbols of the moved string, or `None`
+    /// if the symbol is invalid. This reclaims the symbol without rebuilding the
+    /// interner, but the bytes of the removed string are not freed.
+    ///
+    /// # Note
+    ///
+    /// The former last symbol becomes invalid; its string is now resolved by
+    /// `symbol`. Checkpoints created before the removal must no longer be used
+    /// for [`StringInterner::rollback`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::{backend::BucketBackend, StringInterner};
+    /// let mut interner = StringInterner::<BucketBackend>::new();
+    /// let aa = interner.get_or_intern("aa");
+    /// let bb = interner.get_or_intern("bb");
+    /// let removed = interner.swap_remove(aa).unwrap();
+    /// assert_eq!(removed.string, "aa");
+    /// assert_eq!(removed.moved, Some((bb, aa)));
+    /// assert_eq!(interner.get("bb"), Some(aa));
+    /// assert_eq!(interner.get("aa"), None);
+    /// ```
+    pub fn swap_remove(&mut self, symbol: S) -> Option<SwapRemoved<S>> {
+        self.backend.resolve(symbol)?;
+        let last = self.last_symbol()?;
+        let Self {
+            dedup,
+            hasher,
+            backend,
+            ..
+        } = self;
+        // SAFETY: Both symbols have been checked to be valid for the backend.
+        let hash = unsafe { make_symbol_hash(hasher, backend, symbol) };
+        if let Ok(entry) = dedup.find_entry(hash, |other| *other == symbol) {
+            entry.remove();
+        }
+        if last != symbol {
+            // SAFETY: Both symbols have been checked to be valid for the backend.
+            let hash = unsafe { make_symbol_hash(hasher, backend, last) };
+            if let Some(entry) = dedup.find_mut(hash, |other| *other == last) {
+                *entry = symbol;
+            }
+        }
+        if !self.deduplicate {
+            self.appended -= 1;
+        }
+        self.statics.retain(|(_, other)| *other != symbol);
+        for (_, other) in self.statics.iter_mut() {
+            if *other == last {
+                *other = symbol;
+            }
+        }
+        // The first-seen symbols of the case-folded and NFC strings may have changed.
+        self.folded = None;
+        #[cfg(feature = "unicode")]
+        {
+            self.nfc = None;
+        }
+        #[cfg(feature = "small-string-cache")]
+        self.small.retain(|other| other != symbol && other != last);
+        #[cfg(feature = "generations")]
+        self.generations.swap_remove(symbol, last);
+        #[cfg(feature = "store-hashes")]
+        self.hashes.swap_remove(symbol, last);
+        let string = self.backend.swap_remove(symbol)?;
+        self.removed = true;
+        Some(SwapRemoved {
+            string,
+            moved: (last != symbol).then_some((last, symbol)),
+        })
+    }
+
+    /// Interns the given string without copying its contents if it is not yet interned.
+    ///
+    /// Returns a symbol for resolution into the original string. Useful to merge
+    /// interners without copying:
+    ///
+    /// ```
+    /// # use string_interner::{backend::BucketBackend, StringInterner};
+    /// let mut source = StringInterner::<BucketBackend>::new();
+    /// source.get_or_intern("aa");
+    /// let mut merged = StringInterner::<BucketBackend>::new();
+    /// for (_, string) in source.iter() {
+    ///     // SAFETY: `source` outlives `merged` and is not modified.
+    ///     unsafe { merged.get_or_intern_borrowed(string) };
+    /// }
+    /// assert_eq!(merged.resolve(merged.get("aa").unwrap()), Some("aa"));
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// See [`BucketBackend::intern_borrowed`].
+    ///
+    /// # Panics
+    ///
+    /// See [`StringInterner::get_or_intern`].
     #[inline]
-    pub unsafe fn resolve_unchecked(&self, symbol: <B as Backend<'i>>::Symbol) -> <B as Backend<'i>>::Access<'_> {
-        unsafe { self.backend.resolve_unchecked(symbol) }
+    #[track_caller]
+    pub unsafe fn get_or_intern_borrowed(&mut self, string: &str) -> S {
+        assert!(
+            self.is_within_max_len(string),
+            "string exceeds the maximum string length"
+        );
+        // SAFETY: The caller guarantees that `string` outlives the backend.
+        unsafe { self.get_or_intern_using(InternBorrowed, string) }
     }
 
-    /// Returns an iterator that yields all interned strings and their symbols.
+    /// Interns the given string into `group` if it is not yet interned.
+    ///
+    /// Returns a symbol for resolution into the interned string. New strings
+    /// of the same group are stored next to each other, see
+    /// [`BucketBackend::intern_grouped`]. Strings that are already interned
+    /// keep their symbol and location regardless of their group.
+    ///
+    /// ```
+    /// # use string_interner::{backend::BucketBackend, StringInterner};
+    /// let mut interner = StringInterner::<BucketBackend>::new();
+    /// let a = interner.get_or_intern_grouped(1, "a");
+    /// let _ = interner.get_or_intern("b");
+    /// let c = interner.get_or_intern_grouped(1, "c");
+    /// assert_eq!(interner.get_or_intern_grouped(2, "a"), a);
+    /// assert_eq!(interner.resolve(c), Some("c"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// See [`StringInterner::get_or_intern`].
     #[inline]
-    pub fn iter(&self) -> <B as Backend<'i>>::Iter<'_> {
-        self.backend.iter()
+    #[track_caller]
+    pub fn get_or_intern_grouped<T>(&mut self, group: GroupId, string: T) -> S
+    where
+        T: AsRef<str>,
+    {
+        let string = string.as_ref();
+        assert!(
+            self.is_within_max_len(string),
+            "string exceeds the maximum string length"
+        );
+        // SAFETY: `InternGrouped` has no safety requirements.
+        unsafe { self.get_or_intern_using(InternGrouped(group), string) }
+    }
+
+    /// Interns the formatted `args` without creating an intermediate [`String`].
+    ///
+    /// Returns a symbol for resolution into the formatted string. Use the
+    /// [`get_or_intern_fmt!`](crate::get_or_intern_fmt) macro for more
+    /// convenient call sites.
+    ///
+    /// # Note
+    ///
+    /// The output is formatted directly into a bucket of the backend and then
+    /// deduplicated. If the string has already been interned the written bytes
+    /// are discarded again.
+    ///
+    /// # Panics
+    ///
+    /// See [`StringInterner::get_or_intern`] and [`BucketBackend::intern_fmt`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::{backend::BucketBackend, StringInterner};
+    /// let mut interner = StringInterner::<BucketBackend>::new();
+    /// let (a, b) = ("core", "fmt");
+    /// let sym = interner.get_or_intern_fmt(format_args!("{}::{}", a, b));
+    /// assert_eq!(interner.get_or_intern("core::fmt"), sym);
+    /// ```
+    #[track_caller]
+    pub fn get_or_intern_fmt(&mut self, args: fmt::Arguments<'_>) -> S {
+        if let Some(string) = args.as_str() {
+            return self.get_or_intern_static(string);
+        }
+        let checkpoint = self.backend.checkpoint();
+        let symbol = self.backend.intern_fmt(args);
+        self.dedup_appended(checkpoint, symbol)
+    }
+
+    /// Interns the string assembled from `chars` without creating an intermediate [`String`].
+    ///
+    /// Returns a symbol for resolution into the assembled string. An empty
+    /// iterator interns the empty string.
+    ///
+    /// # Note
+    ///
+    /// The chars are encoded directly into a bucket of the backend and then
+    /// deduplicated. If the string has already been interned the written bytes
+    /// are discarded again.
+    ///
+    /// # Panics
+    ///
+    /// See [`StringInterner::get_or_intern`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::{backend::BucketBackend, StringInterner};
+    /// let mut interner = StringInterner::<BucketBackend>::new();
+    /// let sym = interner.get_or_intern_chars(['f', 'o', 'o']);
+    /// assert_eq!(interner.get_or_intern("foo"), sym);
+    /// ```
+    #[track_caller]
+    pub fn get_or_intern_chars<I>(&mut self, chars: I) -> S
+    where
+        I: IntoIterator<Item = char>,
+    {
+        let checkpoint = self.backend.checkpoint();
+        let symbol = self.backend.intern_chars(chars);
+        self.dedup_appended(checkpoint, symbol)
+    }
+
+    /// Interns the given string and its char-reversed form.
+    ///
+    /// Returns the symbols of `string` and of its reversed form which are
+    /// deduplicated independently, so a palindrome yields the same symbol twice.
+    /// The reversed form is assembled directly in a bucket of the backend, see
+    /// [`StringInterner::get_or_intern_chars`].
+    ///
+    /// # Panics
+    ///
+    /// See [`StringInterner::get_or_intern`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_interner::{backend::BucketBackend, StringInterner};
+    /// let mut interner = StringInterner::<BucketBackend>::new();
+    /// let (abc, cba) = interner.get_or_intern_pair("abc");
+    /// assert_eq!(interner.resolve(cba), Some("cba"));
+    /// assert_eq!(interner.get_or_intern_pair("cba"), (cba, abc));
+    /// ```
+    #[track_caller]
+    pub fn get_or_intern_pair(&mut self, string: &str) -> (S, S) {
+        let forward = self.get_or_intern(string);
+        let reversed = self.get_or_intern_chars(string.chars().rev());
+        (forward, reversed)
+    }
+
+    /// Deduplicates the string of `symbol` that has just been appended to the backend.
+    ///
+    /// If the string has been interned before, or exceeds the maximum string
+    /// length, the backend is rolled back to `checkpoint`.
+    #[track_caller]
+    fn dedup_appended(&mut self, checkpoint: Checkpoint, symbol: S) -> S {
+        if !self.deduplicate {
+            // SAFETY: The symbol has just been created by the backend.
+            let len = unsafe { self.backend.resolve_unchecked(symbol) }.len();
+            if self.max_string_len.is_some_and(|max| len > max) {
+                // SAFETY: The checkpoint has been created right before interning
+                //         the formatted string.
+                unsafe { self.backend.rollback(checkpoint) };
+                panic!("string exceeds the maximum string length");
+            }
+            self.appended += 1;
+            #[cfg(feature = "stats")]
+            self.stats.record(false);
+            self.register_new(symbol, None);
+            return symbol;
+        }
+        let Self {
+            dedup,
+            hasher,
+            backend,
+            max_string_len,
+            ..
+        } = self;
+        // SAFETY: The symbol has just been created by the backend.
+        let string = unsafe { backend.resolve_unchecked(symbol) };
+        let is_within_max_len = max_string_len.is_none_or(|max| string.len() <= max);
+        let hash = make_hash(hasher, string);
+        let existing = dedup
+            .find(hash, |other| {
+                // SAFETY: This is safe because we only operate on symbols that
+                //         we receive from our backend making them valid.
+                string == unsafe { backend.resolve_unchecked(*other) }
+            })
+            .copied();
+        if !is_within_max_len || existing.is_some() {
+            // SAFETY: The checkpoint has been created right before interning
+            //         the formatted string.
+            unsafe { backend.rollback(checkpoint) };
+        }
+        assert!(is_within_max_len, "string exceeds the maximum string length");
+        #[cfg(feature = "stats")]
+        self.stats.record(existing.is_some());
+        if let Some(existing) = existing {
+            return existing;
+        }
+        dedup.insert_unique(hash, symbol, |symbol| {
+            // SAFETY: This is safe because we only operate on symbols that
+            //         we receive from our backend making them valid.
+            unsafe { make_symbol_hash(hasher, backend, *symbol) }
+        });
+        #[cfg(feature = "validate")]
+        self.debug_assert_unique(symbol);
+        self.register_new(symbol, Some(hash));
+        symbol
     }
 }
 
+/// Interns a formatted string using [`StringInterner::get_or_intern_fmt`].
+///
+/// The first argument is the interner, the remaining arguments are passed to
+/// [`format_args!`].
+///
+/// # Example
+///
+/// ```
+/// # use string_interner::{backend::BucketBackend, get_or_intern_fmt, StringInterner};
+/// let mut interner = StringInterner::<BucketBackend>::new();
+/// let sym = get_or_intern_fmt!(interner, "{}::{}", "core", "fmt");
+/// assert_eq!(interner.resolve(sym), Some("core::fmt"));
+/// ```
+#[cfg(feature = "backends")]
+#[macro_export]
+macro_rules! get_or_intern_fmt {
+    ($interner:expr, $($arg:tt)*) => {
+        $interner.get_or_intern_fmt(::core::format_args!($($arg)*))
+    };
+}
+
 impl<'i, B, H, T> FromIterator<T> for StringInterner<'i, B, H>
 where
     B: Backend<'i>,
@@ -328,6 +2729,40 @@ where
     }
 }
 
+/// Allows iterating the strings of a [`StringInterner`] in parallel.
+///
+/// Brings `par_iter` into scope via [`rayon::iter::IntoParallelRefIterator`]
+/// for backends supporting parallel iteration, such as the [`BucketBackend`].
+///
+/// # Example
+///
+/// ```
+/// # use string_interner::{backend::BucketBackend, StringInterner};
+/// use rayon::prelude::*;
+///
+/// let interner = ["a", "bb", "ccc"]
+///     .into_iter()
+///     .collect::<StringInterner<BucketBackend>>();
+/// let total = interner.par_iter().map(|(_, string)| string.len()).sum::<usize>();
+/// assert_eq!(total, 6);
+/// ```
+#[cfg(feature = "rayon")]
+impl<'i, 'l, B, H> rayon::iter::IntoParallelIterator for &'l StringInterner<'i, B, H>
+where
+    B: Backend<'i>,
+    <B as Backend<'i>>::Symbol: Symbol + Send,
+    &'l B: rayon::iter::IntoParallelIterator<Item = (<B as Backend<'i>>::Symbol, &'l str)>,
+    H: BuildHasher,
+{
+    type Item = (<B as Backend<'i>>::Symbol, &'l str);
+    type Iter = <&'l B as rayon::iter::IntoParallelIterator>::Iter;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_par_iter(self) -> Self::Iter {
+        self.backend.into_par_iter()
+    }
+}
+
 impl<'i, 'l, B, H> IntoIterator for &'l StringInterner<'i, B, H>
 where
     B: Backend<'i>,
@@ -343,3 +2778,213 @@ where
         self.backend.into_iter()
     }
 }
+
+/// Lazy formatter returned by [`StringInterner::display`].
+pub struct DisplaySymbol<'a, 'i, B, H>
+where
+    B: Backend<'i>,
+{
+    interner: &'a StringInterner<'i, B, H>,
+    symbol: <B as Backend<'i>>::Symbol,
+}
+
+impl<'i, B, H> Display for DisplaySymbol<'_, 'i, B, H>
+where
+    B: Backend<'i>,
+    <B as Backend<'i>>::Symbol: Symbol,
+    H: BuildHasher,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.interner.resolve(self.symbol) {
+            Some(string) => f.write_str(string.as_ref()),
+            None => write!(f, "<invalid:{}>", self.symbol.to_usize()),
+        }
+    }
+}
+
+/// The number of interned strings at some point, see [`StringInterner::snapshot`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Snapshot {
+    len: usize,
+}
+
+impl Snapshot {
+    /// Returns the number of strings that were interned at the snapshot.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no strings were interned at the snapshot.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// The result of [`StringInterner::swap_remove`].
+#[cfg(feature = "backends")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapRemoved<S> {
+    /// The removed string.
+    pub string: String,
+    /// The old and the new symbol of the string that was moved into the place
+    /// of the removed string.
+    ///
+    /// This is `None` if the removed string was the last one.
+    pub moved: Option<(S, S)>,
+}
+
+/// Handle to a resolved string returned by [`StringInterner::resolved`].
+///
+/// Wraps the [`Access`][Backend::Access] type of the backend and allows
+/// comparisons against `str` and [`String`] without unwrapping it first.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Resolved<A>(A);
+
+impl<A> Resolved<A>
+where
+    A: AsRef<str>,
+{
+    /// Returns the resolved string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.0.as_ref()
+    }
+
+    /// Returns the wrapped backend access value.
+    #[inline]
+    pub fn into_inner(self) -> A {
+        self.0
+    }
+}
+
+impl<A> Deref for Resolved<A>
+where
+    A: AsRef<str>,
+{
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<A> AsRef<str> for Resolved<A>
+where
+    A: AsRef<str>,
+{
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<A> Display for Resolved<A>
+where
+    A: AsRef<str>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<A> PartialEq<str> for Resolved<A>
+where
+    A: AsRef<str>,
+{
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<'a, A> PartialEq<&'a str> for Resolved<A>
+where
+    A: AsRef<str>,
+{
+    #[inline]
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<A> PartialEq<String> for Resolved<A>
+where
+    A: AsRef<str>,
+{
+    #[inline]
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+#[cfg(all(test, feature = "backends"))]
+mod tests {
+    use super::*;
+    use crate::{backend::StringBackend, DefaultSymbol};
+
+    /// A backend answering [`Backend::get`] by scanning its strings.
+    #[derive(Default)]
+    struct ScanBackend<'i>(StringBackend<'i, DefaultSymbol>);
+
+    impl<'i> Backend<'i> for ScanBackend<'i> {
+        type Symbol = DefaultSymbol;
+        type Access<'l>
+            = <StringBackend<'i, DefaultSymbol> as Backend<'i>>::Access<'l>
+        where
+            Self: 'l,
+            'i: 'l;
+        type Iter<'l>
+            = <StringBackend<'i, DefaultSymbol> as Backend<'i>>::Iter<'l>
+        where
+            'i: 'l,
+            Self: 'l;
+
+        fn with_capacity(cap: usize) -> Self {
+            Self(StringBackend::with_capacity(cap))
+        }
+
+        fn intern(&mut self, string: &str) -> Self::Symbol {
+            self.0.intern(string)
+        }
+
+        fn get(&self, string: &str) -> Option<Self::Symbol> {
+            // Pretend that every string starting with `"alias-"` is known.
+            let string = string.strip_prefix("alias-").unwrap_or(string);
+            self.0
+                .iter()
+                .find(|(_, interned)| *interned == string)
+                .map(|(symbol, _)| symbol)
+        }
+
+        fn shrink_to_fit(&mut self) {
+            self.0.shrink_to_fit()
+        }
+
+        fn resolve(&self, symbol: Self::Symbol) -> Option<Self::Access<'_>> {
+            self.0.resolve(symbol)
+        }
+
+        unsafe fn resolve_unchecked(&self, symbol: Self::Symbol) -> Self::Access<'_> {
+            // SAFETY: The function is marked unsafe so that the caller guarantees
+            //         that required invariants are checked.
+            unsafe { self.0.resolve_unchecked(symbol) }
+        }
+
+        fn iter(&self) -> Self::Iter<'_> {
+            self.0.iter()
+        }
+    }
+
+    #[test]
+    fn get_prefers_backend() {
+        let mut interner = StringInterner::<ScanBackend>::new();
+        let aa = interner.get_or_intern("aa");
+        assert_eq!(interner.get("aa"), Some(aa));
+        assert_eq!(interner.get("alias-aa"), Some(aa));
+        assert_eq!(interner.get("bb"), None);
+    }
+}
@@ -130,8 +130,18 @@ extern crate std;
 mod serde_impl;
 
 pub mod backend;
+mod builder;
+mod cached;
+mod error;
 mod interner;
+mod perfect_hash;
+mod report;
+mod side_table;
+mod small;
 pub mod symbol;
+pub mod symbol_map;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 
 /// A convenience [`StringInterner`] type based on the [`DefaultBackend`].
 #[cfg(feature = "backends")]
@@ -140,11 +150,24 @@ pub type DefaultStringInterner<'i, B = DefaultBackend<'i>, H = DefaultHashBuilde
 
 #[cfg(feature = "backends")]
 #[doc(inline)]
-pub use self::backend::DefaultBackend;
+pub use self::{backend::DefaultBackend, cached::CachedInterner, interner::SwapRemoved};
+#[cfg(feature = "perfect-hash")]
+#[doc(inline)]
+pub use self::perfect_hash::{PerfectHashError, PerfectHashInterner};
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use self::builder::DeterministicHashBuilder;
+#[cfg(feature = "stats")]
+#[doc(inline)]
+pub use self::report::InternStats;
 #[doc(inline)]
 pub use self::{
-    interner::StringInterner,
+    builder::StringInternerBuilder,
+    error::{ExceedsCapacityError, InternerError, InvalidSymbolError, OutOfBoundsError},
+    interner::{DisplaySymbol, Resolved, Snapshot, StringInterner},
+    report::CapacityReport,
     symbol::{DefaultSymbol, Symbol},
+    symbol_map::SymbolMap,
 };
 
 #[doc(inline)]
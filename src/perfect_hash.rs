@@ -0,0 +1,283 @@
+#![cfg(feature = "perfect-hash")]
+
+use crate::{backend::Backend, DefaultBackend, StringInterner, Symbol};
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::{
+    convert::TryFrom,
+    fmt,
+    fmt::{Debug, Display, Formatter},
+    hash::BuildHasher,
+};
+use hashbrown::DefaultHashBuilder;
+
+/// The average number of strings per bucket of the hash-and-displace construction.
+const BUCKET_SIZE: usize = 4;
+
+/// The maximum number of displacements tried per bucket before giving up.
+const MAX_DISPLACEMENT: u32 = 1 << 16;
+
+/// Error returned when building a [`PerfectHashInterner`] fails.
+///
+/// Construction fails if two distinct interned strings have the same hash value
+/// or if no collision free displacement could be found for a bucket. Using a
+/// different hasher usually resolves this.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PerfectHashError {
+    bucket: usize,
+}
+
+impl PerfectHashError {
+    /// Returns the index of the bucket that could not be placed.
+    #[inline]
+    pub fn bucket(&self) -> usize {
+        self.bucket
+    }
+}
+
+impl Display for PerfectHashError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to find a perfect hash displacement for bucket {}",
+            self.bucket
+        )
+    }
+}
+
+#[cfg(feature = "error-in-core")]
+impl core::error::Error for PerfectHashError {}
+
+/// Computes the slot index of a string with hash value `hash` for `displacement`.
+#[inline]
+fn slot_index(hash: u64, displacement: u32, len: usize) -> usize {
+    // SplitMix64 finalizer over the hash offset by the displacement.
+    let offset = u64::from(displacement).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    let mut x = hash.wrapping_add(offset);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^= x >> 31;
+    (x % len as u64) as usize
+}
+
+/// Computes the bucket index of a string with hash value `hash`.
+#[inline]
+fn bucket_index(hash: u64, buckets: usize) -> usize {
+    (hash % buckets as u64) as usize
+}
+
+/// An immutable string interner that looks up symbols through a minimal perfect hash.
+///
+/// Built from a [`StringInterner`] via [`TryFrom`]. Supports only
+/// [`get`][PerfectHashInterner::get] and [`resolve`][PerfectHashInterner::resolve],
+/// where `get` hashes the string once and checks exactly one candidate symbol
+/// without any probing.
+///
+/// The perfect hash function is built with the hash-and-displace (CHD) scheme:
+/// strings are grouped into buckets by their hash value and every bucket is
+/// assigned a displacement that moves all of its strings into free slots.
+///
+/// # Example
+///
+/// ```
+/// # use string_interner::{DefaultStringInterner, PerfectHashInterner};
+/// let mut interner = <DefaultStringInterner>::new();
+/// let aa = interner.get_or_intern("aa");
+/// let bb = interner.get_or_intern("bb");
+/// let frozen = PerfectHashInterner::try_from(interner).unwrap();
+/// assert_eq!(frozen.get("aa"), Some(aa));
+/// assert_eq!(frozen.get("bb"), Some(bb));
+/// assert_eq!(frozen.get("cc"), None);
+/// assert_eq!(frozen.resolve(bb), Some("bb"));
+/// ```
+pub struct PerfectHashInterner<'i, B = DefaultBackend<'i>, H = DefaultHashBuilder>
+where
+    B: Backend<'i>,
+{
+    displacements: Box<[u32]>,
+    slots: Box<[<B as Backend<'i>>::Symbol]>,
+    hasher: H,
+    backend: B,
+}
+
+impl<'i, B, H> Debug for PerfectHashInterner<'i, B, H>
+where
+    B: Backend<'i> + Debug,
+    <B as Backend<'i>>::Symbol: Symbol + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PerfectHashInterner")
+            .field("displacements", &self.displacements)
+            .field("slots", &self.slots)
+            .field("backend", &self.backend)
+            .finish()
+    }
+}
+
+impl<'i, B, H> PerfectHashInterner<'i, B, H>
+where
+    B: Backend<'i>,
+    <B as Backend<'i>>::Symbol: Symbol,
+    H: BuildHasher,
+{
+    /// Returns the number of strings in the interner.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns `true` if the interner has no strings.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the symbol for the given string if any.
+    #[inline]
+    pub fn get<T>(&self, string: T) -> Option<<B as Backend<'i>>::Symbol>
+    where
+        T: AsRef<str>,
+    {
+        let string = string.as_ref();
+        if self.is_empty() {
+            return None;
+        }
+        let hash = self.hasher.hash_one(string);
+        let displacement = self.displacements[bucket_index(hash, self.displacements.len())];
+        let symbol = self.slots[slot_index(hash, displacement, self.slots.len())];
+        // SAFETY: All symbols in the slots have been created by the backend.
+        let candidate = unsafe { self.backend.resolve_unchecked(symbol) };
+        (candidate.as_ref() == string).then_some(symbol)
+    }
+
+    /// Returns the string for the given `symbol` if any.
+    #[inline]
+    pub fn resolve(&self, symbol: <B as Backend<'i>>::Symbol) -> Option<<B as Backend<'i>>::Access<'_>> {
+        self.backend.resolve(symbol)
+    }
+
+    /// Returns an iterator that yields all interned strings and their symbols.
+    #[inline]
+    pub fn iter(&self) -> <B as Backend<'i>>::Iter<'_> {
+        self.backend.iter()
+    }
+}
+
+impl<'i, B, H> TryFrom<StringInterner<'i, B, H>> for PerfectHashInterner<'i, B, H>
+where
+    B: Backend<'i>,
+    <B as Backend<'i>>::Symbol: Symbol,
+    H: BuildHasher,
+{
+    type Error = PerfectHashError;
+
+    fn try_from(interner: StringInterner<'i, B, H>) -> Result<Self, Self::Error> {
+        let (backend, hasher, dedup) = interner.into_parts();
+        let len = dedup.len();
+        let num_buckets = len.div_ceil(BUCKET_SIZE).max(1);
+        let mut buckets = vec![Vec::new(); num_buckets];
+        for symbol in dedup {
+            // SAFETY: All symbols of the deduplication table have been created by the backend.
+            let string = unsafe { backend.resolve_unchecked(symbol) };
+            let hash = hasher.hash_one(string.as_ref());
+            buckets[bucket_index(hash, num_buckets)].push((hash, symbol));
+        }
+        // Place the largest buckets first while there are many free slots.
+        let mut order = (0..num_buckets).collect::<Vec<_>>();
+        order.sort_unstable_by_key(|&bucket| core::cmp::Reverse(buckets[bucket].len()));
+        let mut displacements = vec![0; num_buckets].into_boxed_slice();
+        let mut slots = vec![None; len];
+        let mut indices = Vec::with_capacity(BUCKET_SIZE);
+        for bucket in order {
+            let entries = &buckets[bucket];
+            if entries.is_empty() {
+                break;
+            }
+            let displacement = (0..MAX_DISPLACEMENT)
+                .find(|&displacement| {
+                    indices.clear();
+                    entries.iter().all(|&(hash, _)| {
+                        let index = slot_index(hash, displacement, len);
+                        let is_free = slots[index].is_none() && !indices.contains(&index);
+                        indices.push(index);
+                        is_free
+                    })
+                })
+                .ok_or(PerfectHashError { bucket })?;
+            for (&(_, symbol), &index) in entries.iter().zip(&indices) {
+                slots[index] = Some(symbol);
+            }
+            displacements[bucket] = displacement;
+        }
+        let slots = slots
+            .into_iter()
+            .map(|symbol| symbol.expect("all slots are filled by a minimal perfect hash"))
+            .collect();
+        Ok(Self {
+            displacements,
+            slots,
+            hasher,
+            backend,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{BucketBackend, BufferBackend, StringBackend};
+    use alloc::format;
+
+    fn build_and_get<'i, B>()
+    where
+        B: Backend<'i>,
+        <B as Backend<'i>>::Symbol: Symbol + Debug,
+    {
+        let mut interner = StringInterner::<B>::new();
+        let symbols = (0..1000)
+            .map(|i| interner.get_or_intern(format!("string-{}", i)))
+            .collect::<Vec<_>>();
+        let frozen = PerfectHashInterner::try_from(interner).unwrap();
+        assert_eq!(frozen.len(), 1000);
+        for (i, &symbol) in symbols.iter().enumerate() {
+            let string = format!("string-{}", i);
+            assert_eq!(frozen.get(&string), Some(symbol));
+            assert_eq!(frozen.resolve(symbol).unwrap().as_ref(), string);
+        }
+        assert_eq!(frozen.get("string-1000"), None);
+        assert_eq!(frozen.get(""), None);
+    }
+
+    #[test]
+    fn get_works() {
+        build_and_get::<BucketBackend>();
+        build_and_get::<BufferBackend>();
+        build_and_get::<StringBackend>();
+    }
+
+    #[test]
+    fn empty_works() {
+        let frozen = PerfectHashInterner::try_from(StringInterner::<DefaultBackend>::new()).unwrap();
+        assert!(frozen.is_empty());
+        assert_eq!(frozen.get(""), None);
+        assert_eq!(frozen.get("aa"), None);
+    }
+
+    #[test]
+    fn hash_collision_fails() {
+        #[derive(Default)]
+        struct ConstantHasher;
+        impl core::hash::Hasher for ConstantHasher {
+            fn finish(&self) -> u64 {
+                0
+            }
+            fn write(&mut self, _bytes: &[u8]) {}
+        }
+        type ConstantHashBuilder = core::hash::BuildHasherDefault<ConstantHasher>;
+
+        let mut interner = StringInterner::<DefaultBackend, ConstantHashBuilder>::new();
+        interner.get_or_intern("aa");
+        interner.get_or_intern("bb");
+        let error = PerfectHashInterner::try_from(interner).unwrap_err();
+        assert_eq!(error.bucket(), 0);
+    }
+}
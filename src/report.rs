@@ -0,0 +1,45 @@
+/// A snapshot of the size and capacity of a [`StringInterner`](crate::StringInterner).
+///
+/// Returned by [`StringInterner::capacity_report`](crate::StringInterner::capacity_report).
+/// Backends that do not track a metric report it as `0`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct CapacityReport {
+    /// The number of interned strings.
+    pub strings: usize,
+    /// The number of bytes occupied by interned string contents.
+    pub bytes_used: usize,
+    /// The number of bytes allocated for interned string contents.
+    pub bytes_allocated: usize,
+    /// The number of buckets allocated by the backend.
+    pub buckets: usize,
+    /// The number of strings the backend can index without reallocating.
+    pub spans_capacity: usize,
+    /// The number of strings the internal hash map can hold without reallocating.
+    pub map_capacity: usize,
+}
+
+/// The hits and misses of deduplicating lookups of a [`StringInterner`](crate::StringInterner).
+///
+/// Returned by [`StringInterner::stats`](crate::StringInterner::stats). A hit is a
+/// string that has already been interned while a miss is a newly interned string.
+#[cfg(feature = "stats")]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct InternStats {
+    /// The number of interned strings that were already present.
+    pub hits: u64,
+    /// The number of interned strings that were not yet present.
+    pub misses: u64,
+}
+
+#[cfg(feature = "stats")]
+impl InternStats {
+    /// Records a hit if `hit` is `true` and a miss otherwise.
+    #[inline]
+    pub(crate) fn record(&mut self, hit: bool) {
+        if hit {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+    }
+}
@@ -1,9 +1,9 @@
-use crate::{backend::Backend, StringInterner, Symbol};
+use crate::{backend::Backend, CapacityReport, StringInterner, Symbol};
 use alloc::boxed::Box;
 use core::{default::Default, fmt, hash::BuildHasher, marker};
 use serde::{
     de::{Deserialize, Deserializer, SeqAccess, Visitor},
-    ser::{Serialize, SerializeSeq, Serializer},
+    ser::{Serialize, SerializeSeq, SerializeStruct, Serializer},
 };
 
 impl<'i, B, H> Serialize for StringInterner<'i, B, H>
@@ -25,6 +25,22 @@ where
     }
 }
 
+impl Serialize for CapacityReport {
+    fn serialize<T>(&self, serializer: T) -> Result<T::Ok, T::Error>
+    where
+        T: Serializer,
+    {
+        let mut report = serializer.serialize_struct("CapacityReport", 6)?;
+        report.serialize_field("strings", &self.strings)?;
+        report.serialize_field("bytes_used", &self.bytes_used)?;
+        report.serialize_field("bytes_allocated", &self.bytes_allocated)?;
+        report.serialize_field("buckets", &self.buckets)?;
+        report.serialize_field("spans_capacity", &self.spans_capacity)?;
+        report.serialize_field("map_capacity", &self.map_capacity)?;
+        report.end()
+    }
+}
+
 impl<'i: 'de, 'de, B, H> Deserialize<'de> for StringInterner<'i, B, H>
 where
     B: Backend<'i>,
@@ -77,8 +93,8 @@ where
     where
         A: SeqAccess<'de>,
     {
-        let mut interner: StringInterner<B, H> =
-            StringInterner::with_capacity_and_hasher(seq.size_hint().unwrap_or(0), H::default());
+        let mut interner: StringInterner<B, H> = StringInterner::with_hasher(H::default());
+        interner.reserve(seq.size_hint().unwrap_or(0));
         while let Some(s) = seq.next_element::<Box<str>>()? {
             interner.get_or_intern(s);
         }
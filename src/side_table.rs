@@ -0,0 +1,166 @@
+#![cfg(any(feature = "generations", feature = "store-hashes"))]
+
+use crate::Symbol;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use hashbrown::HashMap;
+
+/// Table storing a value per interned string for the [`StringInterner`](crate::StringInterner).
+///
+/// The values are stored in a [`Vec`] in interning order for as long as the
+/// symbols are consecutive indices, as for the
+/// [`BucketBackend`](crate::backend::BucketBackend) and the
+/// [`StringBackend`](crate::backend::StringBackend). The table falls back to a
+/// hash map once a symbol breaks that order, for example the byte offsets of
+/// the [`BufferBackend`](crate::backend::BufferBackend), so that its size is
+/// proportional to the number of strings and not to the symbol values.
+#[derive(Debug, Clone)]
+pub struct SideTable<S, T> {
+    repr: Repr<T>,
+    marker: PhantomData<fn() -> S>,
+}
+
+#[derive(Debug, Clone)]
+enum Repr<T> {
+    /// The value of the symbol with index `i` is at `i`.
+    Dense(Vec<T>),
+    /// The values keyed by the index of their symbol.
+    Sparse(HashMap<usize, T>),
+}
+
+impl<S, T> Default for SideTable<S, T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            repr: Repr::Dense(Vec::new()),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<S, T> SideTable<S, T>
+where
+    S: Symbol,
+{
+    /// Returns the values keyed by the index of their symbol, converting the table if needed.
+    fn sparse(&mut self) -> &mut HashMap<usize, T> {
+        if let Repr::Dense(values) = &mut self.repr {
+            let values = core::mem::take(values).into_iter().enumerate().collect();
+            self.repr = Repr::Sparse(values);
+        }
+        match &mut self.repr {
+            Repr::Sparse(values) => values,
+            Repr::Dense(_) => unreachable!("the table has just been converted"),
+        }
+    }
+
+    /// Inserts `value` for `symbol`.
+    pub fn insert(&mut self, symbol: S, value: T) {
+        let index = symbol.to_usize();
+        match &mut self.repr {
+            Repr::Dense(values) if index == values.len() => values.push(value),
+            Repr::Dense(values) if index < values.len() => values[index] = value,
+            _ => {
+                self.sparse().insert(index, value);
+            }
+        }
+    }
+
+    /// Returns the value for `symbol` if any.
+    #[inline]
+    pub fn get(&self, symbol: S) -> Option<&T> {
+        let index = symbol.to_usize();
+        match &self.repr {
+            Repr::Dense(values) => values.get(index),
+            Repr::Sparse(values) => values.get(&index),
+        }
+    }
+
+    /// Removes the values of all symbols with an index of `len` or above.
+    pub fn truncate(&mut self, len: usize) {
+        match &mut self.repr {
+            Repr::Dense(values) => values.truncate(len),
+            Repr::Sparse(values) => values.retain(|index, _| *index < len),
+        }
+    }
+
+    /// Removes the value for `symbol` and moves the value for `last` in its place.
+    pub fn swap_remove(&mut self, symbol: S, last: S) {
+        let (index, last) = (symbol.to_usize(), last.to_usize());
+        match &mut self.repr {
+            Repr::Dense(values) if index <= last && last + 1 == values.len() => {
+                values.swap_remove(index);
+            }
+            _ => {
+                let values = self.sparse();
+                values.remove(&index);
+                if let Some(value) = values.remove(&last) {
+                    values.insert(index, value);
+                }
+            }
+        }
+    }
+
+    /// Changes the symbol type of the table to `S2`.
+    ///
+    /// The values stay associated with the `usize` representation of their symbols.
+    #[inline]
+    pub fn rebind<S2>(self) -> SideTable<S2, T> {
+        SideTable {
+            repr: self.repr,
+            marker: PhantomData,
+        }
+    }
+
+    /// Shrinks the capacity of the table to fit its values.
+    pub fn shrink_to_fit(&mut self) {
+        match &mut self.repr {
+            Repr::Dense(values) => values.shrink_to_fit(),
+            Repr::Sparse(values) => values.shrink_to_fit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DefaultSymbol;
+
+    fn sym(index: usize) -> DefaultSymbol {
+        DefaultSymbol::try_from_usize(index).unwrap()
+    }
+
+    #[test]
+    fn dense_table_works() {
+        let mut table = SideTable::default();
+        for index in 0..4 {
+            table.insert(sym(index), index * 10);
+        }
+        assert!(matches!(table.repr, Repr::Dense(_)));
+        table.swap_remove(sym(1), sym(3));
+        assert_eq!(table.get(sym(1)), Some(&30));
+        assert_eq!(table.get(sym(3)), None);
+        table.truncate(1);
+        assert_eq!(table.get(sym(0)), Some(&0));
+        assert_eq!(table.get(sym(1)), None);
+        assert!(matches!(table.repr, Repr::Dense(_)));
+    }
+
+    #[test]
+    fn sparse_table_works() {
+        let mut table = SideTable::default();
+        for index in [0, 10, 25, 40] {
+            table.insert(sym(index), index);
+        }
+        assert!(matches!(table.repr, Repr::Sparse(_)));
+        assert_eq!(table.get(sym(0)), Some(&0));
+        assert_eq!(table.get(sym(25)), Some(&25));
+        assert_eq!(table.get(sym(1)), None);
+        table.swap_remove(sym(10), sym(40));
+        assert_eq!(table.get(sym(10)), Some(&40));
+        assert_eq!(table.get(sym(40)), None);
+        table.truncate(25);
+        assert_eq!(table.get(sym(25)), None);
+        assert_eq!(table.get(sym(10)), Some(&40));
+    }
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "small-string-cache")]
+
+use crate::Symbol;
+use alloc::{boxed::Box, vec};
+
+/// The number of distinct ASCII characters.
+const ASCII: usize = 128;
+
+/// The number of slots of the table: one per ASCII string of length 1 or 2.
+const SLOTS: usize = ASCII + ASCII * ASCII;
+
+/// Direct-indexed table caching the symbols of ASCII strings of length 1 and 2.
+///
+/// Allows the [`StringInterner`](crate::StringInterner) to look up the most
+/// common tiny strings without hashing them. The table is only a cache: a miss
+/// does not mean that the string has not been interned.
+///
+/// The table is allocated on the first insertion and occupies
+/// `SLOTS * size_of::<Option<S>>()` bytes from then on.
+#[derive(Debug, Clone)]
+pub struct SmallStrings<S> {
+    slots: Option<Box<[Option<S>]>>,
+}
+
+impl<S> Default for SmallStrings<S> {
+    #[inline]
+    fn default() -> Self {
+        Self { slots: None }
+    }
+}
+
+impl<S> SmallStrings<S>
+where
+    S: Symbol,
+{
+    /// Returns the slot index of `string` if it is cached by the table.
+    #[inline]
+    fn index(string: &str) -> Option<usize> {
+        match *string.as_bytes() {
+            [a] if a.is_ascii() => Some(usize::from(a)),
+            [a, b] if a.is_ascii() && b.is_ascii() => {
+                Some(ASCII + usize::from(a) * ASCII + usize::from(b))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the cached symbol of `string` if any.
+    #[inline]
+    pub fn get(&self, string: &str) -> Option<S> {
+        let slots = self.slots.as_ref()?;
+        slots[Self::index(string)?]
+    }
+
+    /// Caches `symbol` for `string` if `string` is cached by the table.
+    #[inline]
+    pub fn insert(&mut self, string: &str, symbol: S) {
+        let Some(index) = Self::index(string) else {
+            return;
+        };
+        let slots = self
+            .slots
+            .get_or_insert_with(|| vec![None; SLOTS].into_boxed_slice());
+        slots[index] = Some(symbol);
+    }
+
+    /// Removes all cached symbols that do not satisfy `f`.
+    pub fn retain(&mut self, mut f: impl FnMut(S) -> bool) {
+        for slot in self.slots.iter_mut().flat_map(|slots| slots.iter_mut()) {
+            if slot.is_some_and(|symbol| !f(symbol)) {
+                *slot = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DefaultSymbol;
+
+    fn sym(index: usize) -> DefaultSymbol {
+        DefaultSymbol::try_from_usize(index).unwrap()
+    }
+
+    #[test]
+    fn get_and_insert_work() {
+        let mut small = SmallStrings::default();
+        assert_eq!(small.get("a"), None);
+        small.insert("a", sym(0));
+        small.insert("ab", sym(1));
+        small.insert("abc", sym(2));
+        small.insert("ß", sym(3));
+        small.insert("", sym(4));
+        assert_eq!(small.get("a"), Some(sym(0)));
+        assert_eq!(small.get("ab"), Some(sym(1)));
+        assert_eq!(small.get("ba"), None);
+        assert_eq!(small.get("abc"), None);
+        assert_eq!(small.get("ß"), None);
+        assert_eq!(small.get(""), None);
+        assert_eq!(small.get("\x7f\x7f"), None);
+        small.insert("\x7f\x7f", sym(5));
+        assert_eq!(small.get("\x7f\x7f"), Some(sym(5)));
+    }
+
+    #[test]
+    fn retain_works() {
+        let mut small = SmallStrings::default();
+        small.insert("a", sym(0));
+        small.insert("b", sym(1));
+        small.retain(|symbol| symbol.to_usize() < 1);
+        assert_eq!(small.get("a"), Some(sym(0)));
+        assert_eq!(small.get("b"), None);
+    }
+}
@@ -17,6 +17,20 @@ use core::num::{NonZeroU16, NonZeroU32, NonZeroUsize};
 ///
 /// Optimal symbols allow for efficient comparisons and have a small memory footprint.
 pub trait Symbol: Copy + Eq {
+    /// The largest index that can be represented by the symbol.
+    ///
+    /// An interner using this symbol type can hold at most `MAX_INDEX + 1`
+    /// strings.
+    ///
+    /// # Note
+    ///
+    /// Defaults to `usize::MAX` so that existing implementations keep
+    /// compiling. Symbols that cannot represent every `usize` should override
+    /// it, otherwise exhausting them is only detected by
+    /// [`try_from_usize`](`Symbol::try_from_usize`) after the string has been
+    /// written to the backend.
+    const MAX_INDEX: usize = usize::MAX;
+
     /// Creates a symbol from a `usize`.
     ///
     /// Returns `None` if `index` is out of bounds for the symbol.
@@ -33,6 +47,7 @@ pub trait Symbol: Copy + Eq {
 /// Panics if the conversion is invalid.
 #[cfg(feature = "backends")]
 #[inline]
+#[track_caller]
 pub(crate) fn expect_valid_symbol<S>(index: usize) -> S
 where
     S: Symbol,
@@ -67,10 +82,14 @@ macro_rules! gen_symbol_for {
         }
 
         impl Symbol for $name {
+            const MAX_INDEX: usize = <$base_ty>::MAX as usize - 1;
+
             #[inline]
             fn try_from_usize(index: usize) -> Option<Self> {
-                <$non_zero>::new((index as $base_ty).wrapping_add(1))
-                    .map(|value| Self { value })
+                if index > Self::MAX_INDEX {
+                    return None;
+                }
+                <$non_zero>::new(index as $base_ty + 1).map(|value| Self { value })
             }
 
             #[inline]
@@ -99,6 +118,77 @@ gen_symbol_for!(
     struct SymbolUsize(NonZeroUsize; usize);
 );
 
+/// Resolves to the smallest symbol type able to represent `count` distinct symbols.
+///
+/// Expands to [`SymbolU16`] if `count <= SymbolU16::MAX_INDEX + 1`, else to
+/// [`SymbolU32`] if `count <= SymbolU32::MAX_INDEX + 1` and to [`SymbolUsize`]
+/// otherwise. The `count` must be a constant expression.
+///
+/// # Note
+///
+/// A plain type alias generic over the count is not expressible on stable Rust
+/// which is why this is a macro. Generic code can still be parameterized over
+/// the resulting symbol type.
+///
+/// # Example
+///
+/// ```
+/// # use string_interner::{backend::StringBackend, smallest_symbol, symbol::SymbolU16, StringInterner};
+/// type Symbol = smallest_symbol!(1000);
+/// let mut interner = StringInterner::<StringBackend<Symbol>>::new();
+/// let sym: SymbolU16 = interner.get_or_intern("Banana");
+/// ```
+#[macro_export]
+macro_rules! smallest_symbol {
+    ($count:expr) => {
+        <$crate::symbol::SymbolWidth<{ $crate::symbol::smallest_symbol_width($count) }>
+            as $crate::symbol::SelectSymbol>::Symbol
+    };
+}
+
+/// Returns the size in bytes of the smallest symbol type able to represent
+/// `count` distinct symbols.
+///
+/// Used by [`smallest_symbol!`].
+#[doc(hidden)]
+pub const fn smallest_symbol_width(count: usize) -> usize {
+    if count <= SymbolU16::MAX_INDEX + 1 {
+        2
+    } else if count <= SymbolU32::MAX_INDEX + 1 {
+        4
+    } else {
+        core::mem::size_of::<usize>()
+    }
+}
+
+/// Marker type selecting a symbol type by its size in bytes.
+///
+/// Used by [`smallest_symbol!`].
+#[doc(hidden)]
+pub struct SymbolWidth<const BYTES: usize>;
+
+/// Maps a [`SymbolWidth`] to its symbol type.
+///
+/// Used by [`smallest_symbol!`].
+#[doc(hidden)]
+pub trait SelectSymbol {
+    /// The selected symbol type.
+    type Symbol: Symbol;
+}
+
+impl SelectSymbol for SymbolWidth<2> {
+    type Symbol = SymbolU16;
+}
+
+#[cfg(not(target_pointer_width = "32"))]
+impl SelectSymbol for SymbolWidth<4> {
+    type Symbol = SymbolU32;
+}
+
+impl SelectSymbol for SymbolWidth<{ core::mem::size_of::<usize>() }> {
+    type Symbol = SymbolUsize;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,6 +222,8 @@ mod tests {
             })
         );
         assert_eq!(SymbolU16::try_from_usize(u16::MAX as usize), None);
+        assert_eq!(SymbolU16::try_from_usize(1 << 16), None);
+        assert_eq!(SymbolU16::try_from_usize((1 << 17) + 1), None);
         assert_eq!(SymbolU16::try_from_usize(usize::MAX), None);
     }
 
@@ -155,6 +247,8 @@ mod tests {
                 );
                 assert_eq!(<$name>::try_from_usize(<$base_ty>::MAX as usize), None);
                 assert_eq!(<$name>::try_from_usize(<usize>::MAX), None);
+                assert!(<$name>::try_from_usize(<$name>::MAX_INDEX).is_some());
+                assert!(<$name>::try_from_usize(<$name>::MAX_INDEX + 1).is_none());
             }
         };
     }
@@ -170,4 +264,72 @@ mod tests {
         try_from_usize_works_for_usize:
         struct SymbolUsize(NonZeroUsize; usize);
     );
+
+    #[test]
+    #[cfg(feature = "test-util")]
+    fn symbols_roundtrip() {
+        use crate::test_util::assert_symbol_roundtrip;
+
+        assert_symbol_roundtrip::<SymbolU16>(usize::MAX);
+        assert_symbol_roundtrip::<SymbolU32>(1000);
+        assert_symbol_roundtrip::<SymbolUsize>(1000);
+        assert_symbol_roundtrip::<usize>(1000);
+    }
+
+    #[test]
+    #[cfg(feature = "test-util")]
+    #[should_panic(expected = "above MAX_INDEX is accepted")]
+    fn symbol_roundtrip_rejects_truncation() {
+        #[derive(Copy, Clone, PartialEq, Eq)]
+        struct Truncating(u16);
+
+        impl Symbol for Truncating {
+            const MAX_INDEX: usize = u16::MAX as usize - 1;
+
+            fn try_from_usize(index: usize) -> Option<Self> {
+                let index = index as u16;
+                (index != u16::MAX).then_some(Self(index))
+            }
+
+            fn to_usize(self) -> usize {
+                usize::from(self.0)
+            }
+        }
+
+        crate::test_util::assert_symbol_roundtrip::<Truncating>(10);
+    }
+
+    #[test]
+    fn max_index_defaults_to_usize_max() {
+        #[derive(Copy, Clone, PartialEq, Eq)]
+        struct Custom(usize);
+
+        impl Symbol for Custom {
+            fn try_from_usize(index: usize) -> Option<Self> {
+                Some(Self(index))
+            }
+
+            fn to_usize(self) -> usize {
+                self.0
+            }
+        }
+
+        assert_eq!(Custom::MAX_INDEX, usize::MAX);
+    }
+
+    #[test]
+    fn smallest_symbol_works() {
+        assert_eq!(size_of::<smallest_symbol!(0)>(), 2);
+        assert_eq!(size_of::<smallest_symbol!(1000)>(), 2);
+        assert_eq!(size_of::<smallest_symbol!(SymbolU16::MAX_INDEX + 1)>(), 2);
+        assert_eq!(
+            size_of::<smallest_symbol!(SymbolU16::MAX_INDEX + 2)>(),
+            size_of::<SymbolU32>()
+        );
+        assert_eq!(
+            size_of::<smallest_symbol!(usize::MAX)>(),
+            size_of::<SymbolUsize>()
+        );
+        let _: smallest_symbol!(1000) = SymbolU16::try_from_usize(0).unwrap();
+    }
 }
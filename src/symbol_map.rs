@@ -0,0 +1,285 @@
+//! A dense map from symbols to values, see [`SymbolMap`].
+
+use crate::{symbol::Symbol, DefaultSymbol};
+use alloc::vec::Vec;
+use core::{iter::Enumerate, marker::PhantomData, slice};
+
+/// A dense map from symbols to values.
+///
+/// Stores the values in a [`Vec`] indexed by the `usize` representation of the
+/// symbols and grows it on demand. This is the common companion to a
+/// [`StringInterner`](crate::StringInterner) to associate data with interned
+/// strings.
+///
+/// # Note
+///
+/// The map is only dense for backends that create consecutive symbols such as
+/// the [`StringBackend`](crate::backend::StringBackend) and the
+/// [`BucketBackend`](crate::backend::BucketBackend). The symbols of the
+/// [`BufferBackend`](crate::backend::BufferBackend) are byte offsets which
+/// leaves gaps in the map.
+///
+/// # Example
+///
+/// ```
+/// # use string_interner::{DefaultStringInterner, SymbolMap};
+/// let mut interner = DefaultStringInterner::default();
+/// let mut lengths = SymbolMap::new();
+/// let aa = interner.get_or_intern("aa");
+/// lengths.insert(aa, 2);
+/// assert_eq!(lengths.get(aa), Some(&2));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SymbolMap<S = DefaultSymbol, V = ()> {
+    values: Vec<Option<V>>,
+    len: usize,
+    marker: PhantomData<fn() -> S>,
+}
+
+impl<S, V> Default for SymbolMap<S, V> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            values: Vec::new(),
+            len: 0,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<S, V> SymbolMap<S, V>
+where
+    S: Symbol,
+{
+    /// Creates a new empty symbol map.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new empty symbol map with space for symbols with indices below `cap`.
+    #[inline]
+    pub fn with_capacity(cap: usize) -> Self {
+        Self {
+            values: Vec::with_capacity(cap),
+            len: 0,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of values in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the map contains no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Inserts `value` for `symbol` and returns the previous value if any.
+    ///
+    /// Grows the map if `symbol` is out of its current bounds.
+    pub fn insert(&mut self, symbol: S, value: V) -> Option<V> {
+        let index = symbol.to_usize();
+        if index >= self.values.len() {
+            self.values.resize_with(index + 1, || None);
+        }
+        let old = self.values[index].replace(value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    /// Removes the value for `symbol` and returns it if any.
+    pub fn remove(&mut self, symbol: S) -> Option<V> {
+        let old = self.values.get_mut(symbol.to_usize())?.take();
+        if old.is_some() {
+            self.len -= 1;
+        }
+        old
+    }
+
+    /// Returns `true` if the map contains a value for `symbol`.
+    #[inline]
+    pub fn contains_key(&self, symbol: S) -> bool {
+        self.get(symbol).is_some()
+    }
+
+    /// Returns a shared reference to the value for `symbol` if any.
+    #[inline]
+    pub fn get(&self, symbol: S) -> Option<&V> {
+        self.values.get(symbol.to_usize())?.as_ref()
+    }
+
+    /// Returns an exclusive reference to the value for `symbol` if any.
+    #[inline]
+    pub fn get_mut(&mut self, symbol: S) -> Option<&mut V> {
+        self.values.get_mut(symbol.to_usize())?.as_mut()
+    }
+
+    /// Removes all values from the map.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.len = 0;
+    }
+
+    /// Returns an iterator over the symbols and values of the map in symbol order.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, S, V> {
+        Iter {
+            values: self.values.iter().enumerate(),
+            remaining: self.len,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, S, V> IntoIterator for &'a SymbolMap<S, V>
+where
+    S: Symbol,
+{
+    type Item = (S, &'a V);
+    type IntoIter = Iter<'a, S, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<S, V> FromIterator<(S, V)> for SymbolMap<S, V>
+where
+    S: Symbol,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (S, V)>,
+    {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<S, V> Extend<(S, V)> for SymbolMap<S, V>
+where
+    S: Symbol,
+{
+    /// Inserts all pairs of `iter`, later values replacing earlier ones for
+    /// the same symbol.
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (S, V)>,
+    {
+        for (symbol, value) in iter {
+            self.insert(symbol, value);
+        }
+    }
+}
+
+/// Iterator over the symbols and values of a [`SymbolMap`].
+pub struct Iter<'a, S, V> {
+    values: Enumerate<slice::Iter<'a, Option<V>>>,
+    remaining: usize,
+    marker: PhantomData<fn() -> S>,
+}
+
+impl<'a, S, V> Iterator for Iter<'a, S, V>
+where
+    S: Symbol,
+{
+    type Item = (S, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, value) in self.values.by_ref() {
+            if let Some(value) = value {
+                self.remaining -= 1;
+                let symbol = S::try_from_usize(index).expect("encountered invalid symbol");
+                return Some((symbol, value));
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<S, V> ExactSizeIterator for Iter<'_, S, V> where S: Symbol {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn sym(index: usize) -> DefaultSymbol {
+        DefaultSymbol::try_from_usize(index).unwrap()
+    }
+
+    #[test]
+    fn insert_and_get_work() {
+        let mut map = SymbolMap::new();
+        assert!(map.is_empty());
+        assert_eq!(map.insert(sym(3), "c"), None);
+        assert_eq!(map.insert(sym(0), "a"), None);
+        assert_eq!(map.insert(sym(3), "C"), Some("c"));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(sym(0)), Some(&"a"));
+        assert_eq!(map.get(sym(1)), None);
+        assert_eq!(map.get(sym(3)), Some(&"C"));
+        assert_eq!(map.get(sym(100)), None);
+        *map.get_mut(sym(0)).unwrap() = "A";
+        assert_eq!(map.get(sym(0)), Some(&"A"));
+        assert!(map.contains_key(sym(3)));
+        assert!(!map.contains_key(sym(2)));
+    }
+
+    #[test]
+    fn remove_works() {
+        let mut map = SymbolMap::new();
+        map.insert(sym(1), 10);
+        assert_eq!(map.remove(sym(1)), Some(10));
+        assert_eq!(map.remove(sym(1)), None);
+        assert_eq!(map.remove(sym(100)), None);
+        assert!(map.is_empty());
+        map.insert(sym(2), 20);
+        map.clear();
+        assert!(map.is_empty());
+        assert_eq!(map.get(sym(2)), None);
+    }
+
+    #[test]
+    fn from_iter_and_extend_work() {
+        let mut map = [(sym(4), 'b'), (sym(1), 'a'), (sym(4), 'c')]
+            .into_iter()
+            .collect::<SymbolMap<_, _>>();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(sym(4)), Some(&'c'));
+        map.extend([(sym(9), 'd'), (sym(1), 'e')]);
+        assert_eq!(map.len(), 3);
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            vec![(sym(1), &'e'), (sym(4), &'c'), (sym(9), &'d')]
+        );
+    }
+
+    #[test]
+    fn iter_works() {
+        let mut map = SymbolMap::new();
+        map.insert(sym(5), 'b');
+        map.insert(sym(2), 'a');
+        map.insert(sym(7), 'c');
+        map.remove(sym(7));
+        let iter = map.iter();
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![(sym(2), &'a'), (sym(5), &'b')]);
+    }
+}
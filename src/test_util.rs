@@ -0,0 +1,77 @@
+//! Helpers to test custom [`Symbol`] implementations.
+//!
+//! Downstream crates implementing [`Symbol`] for their own types can reuse
+//! these to check that their symbols uphold the same guarantees as the
+//! symbols of this crate.
+
+use crate::Symbol;
+use proptest::strategy::Strategy;
+
+/// Asserts that the symbol type `S` round-trips its valid indices.
+///
+/// Checks that [`Symbol::try_from_usize`] followed by [`Symbol::to_usize`]
+/// returns the original index for all indices up to `max_check` as well as
+/// for the indices up to [`Symbol::MAX_INDEX`] closest to it. Also checks
+/// that indices above `MAX_INDEX` are rejected, including indices that a
+/// truncating conversion would fold onto valid ones such as
+/// `2 * (MAX_INDEX + 1)` and `usize::MAX - 1`.
+///
+/// Symbols that can represent fewer indices than `usize` must override
+/// [`Symbol::MAX_INDEX`] to pass this check.
+///
+/// # Panics
+///
+/// If `S` violates any of the above.
+///
+/// # Example
+///
+/// ```
+/// # use string_interner::{symbol::SymbolU16, test_util::assert_symbol_roundtrip};
+/// assert_symbol_roundtrip::<SymbolU16>(1000);
+/// ```
+#[track_caller]
+pub fn assert_symbol_roundtrip<S>(max_check: usize)
+where
+    S: Symbol,
+{
+    let max_check = max_check.min(S::MAX_INDEX);
+    let upper = S::MAX_INDEX.saturating_sub(max_check).max(max_check);
+    for index in (0..=max_check).chain(upper..=S::MAX_INDEX) {
+        let symbol = S::try_from_usize(index)
+            .unwrap_or_else(|| panic!("index {} is rejected but not above MAX_INDEX", index));
+        assert_eq!(symbol.to_usize(), index, "index {} does not round-trip", index);
+    }
+    let len = S::MAX_INDEX.checked_add(1);
+    let invalid = [
+        len,
+        len.and_then(|len| len.checked_mul(2)),
+        len.and_then(|len| len.checked_mul(2)?.checked_add(1)),
+        Some(usize::MAX - 1),
+        Some(usize::MAX),
+    ];
+    for index in invalid.into_iter().flatten().filter(|&index| index > S::MAX_INDEX) {
+        assert!(
+            S::try_from_usize(index).is_none(),
+            "index {} above MAX_INDEX is accepted",
+            index
+        );
+    }
+}
+
+/// Returns a [`proptest`] strategy generating all valid indices of the symbol type `S`.
+///
+/// # Example
+///
+/// ```
+/// # use proptest::prelude::*;
+/// # use string_interner::{symbol::SymbolU16, test_util::symbol_index, Symbol};
+/// proptest!(|(index in symbol_index::<SymbolU16>())| {
+///     prop_assert_eq!(SymbolU16::try_from_usize(index).map(Symbol::to_usize), Some(index));
+/// });
+/// ```
+pub fn symbol_index<S>() -> impl Strategy<Value = usize>
+where
+    S: Symbol,
+{
+    0..=S::MAX_INDEX
+}
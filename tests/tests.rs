@@ -1,7 +1,7 @@
 mod allocator;
 
 use allocator::TracingAllocator;
-use string_interner::{backend, DefaultHashBuilder, DefaultSymbol, Symbol};
+use string_interner::{backend, DefaultHashBuilder, DefaultSymbol, InternerError, Symbol};
 
 #[global_allocator]
 static ALLOCATOR: TracingAllocator = TracingAllocator::new();
@@ -33,27 +33,40 @@ pub trait BackendStats {
     const NAME: &'static str;
 }
 
+/// The number of per-string tables kept by the interner for the enabled crate features.
+const SIDE_TABLES: usize =
+    cfg!(feature = "generations") as usize + cfg!(feature = "store-hashes") as usize;
+
+/// The memory overhead of a per-string table for backends with consecutive symbols.
+///
+/// Stores a `u64` per 20 byte word of the memory consumption test.
+const DENSE_TABLE_OVERHEAD: f64 = 0.4;
+
 impl BackendStats for backend::BucketBackend<'_, DefaultSymbol> {
-    const MIN_OVERHEAD: f64 = 2.2;
-    const MAX_OVERHEAD: f64 = 3.1;
-    const MAX_ALLOCATIONS: usize = 65;
-    const MAX_DEALLOCATIONS: usize = 42;
+    const MIN_OVERHEAD: f64 = 2.2 + SIDE_TABLES as f64 * DENSE_TABLE_OVERHEAD;
+    const MAX_OVERHEAD: f64 = 3.1 + SIDE_TABLES as f64 * DENSE_TABLE_OVERHEAD;
+    // Inline spans save the first allocations of the spans.
+    const MAX_ALLOCATIONS: usize =
+        if cfg!(feature = "smallvec-spans") { 63 } else { 65 } + SIDE_TABLES * 20;
+    const MAX_DEALLOCATIONS: usize =
+        if cfg!(feature = "smallvec-spans") { 40 } else { 42 } + SIDE_TABLES * 19;
     const NAME: &'static str = "BucketBackend";
 }
 
 impl BackendStats for backend::StringBackend<'_, DefaultSymbol> {
-    const MIN_OVERHEAD: f64 = 1.7;
-    const MAX_OVERHEAD: f64 = 1.93;
-    const MAX_ALLOCATIONS: usize = 62;
-    const MAX_DEALLOCATIONS: usize = 59;
+    const MIN_OVERHEAD: f64 = 1.7 + SIDE_TABLES as f64 * DENSE_TABLE_OVERHEAD;
+    const MAX_OVERHEAD: f64 = 1.93 + SIDE_TABLES as f64 * DENSE_TABLE_OVERHEAD;
+    const MAX_ALLOCATIONS: usize = 62 + SIDE_TABLES * 20;
+    const MAX_DEALLOCATIONS: usize = 59 + SIDE_TABLES * 19;
     const NAME: &'static str = "StringBackend";
 }
 
+// The byte offset symbols of the buffer backend are stored in hash maps.
 impl BackendStats for backend::BufferBackend<'_, DefaultSymbol> {
-    const MIN_OVERHEAD: f64 = 1.35;
-    const MAX_OVERHEAD: f64 = 1.58;
-    const MAX_ALLOCATIONS: usize = 43;
-    const MAX_DEALLOCATIONS: usize = 41;
+    const MIN_OVERHEAD: f64 = 1.35 + SIDE_TABLES as f64 * 1.0;
+    const MAX_OVERHEAD: f64 = 1.58 + SIDE_TABLES as f64 * 1.8;
+    const MAX_ALLOCATIONS: usize = 43 + SIDE_TABLES * 21;
+    const MAX_DEALLOCATIONS: usize = 41 + SIDE_TABLES * 20;
     const NAME: &'static str = "BufferBackend";
 }
 
@@ -122,6 +135,18 @@ macro_rules! gen_tests_for_backend {
             }
         }
 
+        #[test]
+        #[cfg_attr(any(miri, not(feature = "test-allocations")), ignore)]
+        fn new_does_not_allocate() {
+            ALLOCATOR.reset();
+            ALLOCATOR.start_profiling();
+            let mut interner = StringInterner::new();
+            assert_eq!(ALLOCATOR.stats().len_allocations(), 0);
+            interner.get_or_intern("aa");
+            ALLOCATOR.end_profiling();
+            assert_ne!(ALLOCATOR.stats().len_allocations(), 0);
+        }
+
         #[test]
         #[cfg_attr(any(miri, not(feature = "test-allocations")), ignore)]
         fn test_memory_consumption() {
@@ -301,6 +326,23 @@ macro_rules! gen_tests_for_backend {
             assert_eq!(interner.resolve(dd), None);
         }
 
+        #[test]
+        fn resolved_works() {
+            let mut interner = StringInterner::new();
+            let aa = interner.get_or_intern("aa");
+            let bb = interner.get_or_intern("bb");
+            let resolved = interner.resolved(aa).unwrap();
+            assert!(resolved == "aa");
+            assert!(resolved == *"aa");
+            let owned = String::from("aa");
+            assert!(resolved == owned);
+            assert!(resolved != "bb");
+            assert_eq!(resolved.len(), 2);
+            assert_eq!(format!("{}", resolved), "aa");
+            assert_eq!(interner.resolved(bb).map(|s| s.to_uppercase()), Some(String::from("BB")));
+            assert!(interner.resolved(expect_valid_symbol(1000)).is_none());
+        }
+
         #[test]
         fn resolve_unchecked_works() {
             let mut interner = StringInterner::new();
@@ -384,6 +426,797 @@ macro_rules! gen_tests_for_backend {
             assert!(Iterator::eq(expected_iter, &interner));
         }
 
+        #[test]
+        fn iter_insertion_order_works() {
+            let mut interner = StringInterner::new();
+            let cc = interner.get_or_intern("cc");
+            let aa = interner.get_or_intern("aa");
+            let bb = interner.get_or_intern_static("bb");
+            interner.get_or_intern("aa");
+            assert!(cc < aa && aa < bb);
+            let entries = interner.iter_insertion_order().unwrap().collect::<Vec<_>>();
+            assert_eq!(entries, [(cc, "cc"), (aa, "aa"), (bb, "bb")]);
+        }
+
+        #[test]
+        fn merge_works() {
+            let source = ["aa", "bb", "cc", "dd"].into_iter().collect::<StringInterner>();
+            let build_destination = || {
+                let mut interner = StringInterner::new();
+                interner.get_or_intern("cc");
+                interner.get_or_intern("xx");
+                interner
+            };
+            let mut lhs = build_destination();
+            let mut rhs = build_destination();
+            lhs.merge(&source);
+            rhs.merge(&source);
+            assert_eq!(lhs.len(), 5);
+            assert!(Iterator::eq(lhs.iter(), rhs.iter()));
+            // New strings keep their relative order from `source`.
+            let aa = lhs.get("aa").unwrap().to_usize();
+            let bb = lhs.get("bb").unwrap().to_usize();
+            let dd = lhs.get("dd").unwrap().to_usize();
+            assert!(aa < bb && bb < dd);
+            assert_eq!(lhs.get("cc").unwrap().to_usize(), 0);
+        }
+
+        #[test]
+        fn first_and_last_symbol_work() {
+            let mut interner = StringInterner::new();
+            assert_eq!(interner.first_symbol(), None);
+            assert_eq!(interner.last_symbol(), None);
+            let aa = interner.get_or_intern("aa");
+            assert_eq!(interner.first_symbol(), Some(aa));
+            assert_eq!(interner.last_symbol(), Some(aa));
+            interner.get_or_intern("bb");
+            let cc = interner.get_or_intern("cc");
+            interner.get_or_intern("aa");
+            assert_eq!(interner.first_symbol(), Some(aa));
+            assert_eq!(interner.last_symbol(), Some(cc));
+            assert_eq!(interner.iter().last(), Some((cc, "cc")));
+        }
+
+        #[test]
+        fn resolve_bytes_works() {
+            let mut interner = StringInterner::new();
+            let aa = interner.get_or_intern("aa");
+            let empty = interner.get_or_intern("");
+            let unicode = interner.get_or_intern("ß");
+            assert_eq!(interner.resolve_bytes(aa), Some(&b"aa"[..]));
+            assert_eq!(interner.resolve_bytes(empty), Some(&b""[..]));
+            assert_eq!(interner.resolve_bytes(unicode), Some("ß".as_bytes()));
+            let invalid = <$symbol>::try_from_usize(1000).unwrap();
+            assert_eq!(interner.resolve_bytes(invalid), None);
+        }
+
+        #[test]
+        fn since_works() {
+            let mut interner = StringInterner::new();
+            let empty = interner.snapshot();
+            assert!(empty.is_empty());
+            let aa = interner.get_or_intern("aa");
+            let snapshot = interner.snapshot();
+            assert_eq!(snapshot.len(), 1);
+            assert_eq!(interner.since(&snapshot).next(), None);
+            let bb = interner.get_or_intern("bb");
+            interner.get_or_intern("aa");
+            let cc = interner.get_or_intern("cc");
+            assert_eq!(
+                interner.since(&snapshot).collect::<Vec<_>>(),
+                [(bb, "bb"), (cc, "cc")]
+            );
+            assert_eq!(interner.since(&empty).next(), Some((aa, "aa")));
+        }
+
+        #[test]
+        fn with_symbol_type_works() {
+            use string_interner::symbol::SymbolUsize;
+            let widen = |symbol: $symbol| SymbolUsize::try_from_usize(symbol.to_usize()).unwrap();
+            let mut interner = StringInterner::new();
+            let aa = interner.get_or_intern("aa");
+            let bb = interner.get_or_intern_static("bb");
+            let mut interner = interner.with_symbol_type::<SymbolUsize>().unwrap();
+            assert_eq!(interner.get("aa"), Some(widen(aa)));
+            assert_eq!(interner.resolve(widen(aa)), Some("aa"));
+            assert_eq!(interner.get_or_intern_static("bb"), widen(bb));
+            let cc = interner.get_or_intern("cc");
+            assert_eq!(interner.resolve(cc), Some("cc"));
+            assert_eq!(interner.len(), 3);
+        }
+
+        #[test]
+        fn resolve_cow_works() {
+            use std::borrow::Cow;
+            let mut interner = StringInterner::new();
+            let aa = interner.get_or_intern("aa");
+            assert!(matches!(interner.resolve_cow(aa), Some(Cow::Borrowed("aa"))));
+            let invalid = <$symbol>::try_from_usize(1000).unwrap();
+            assert_eq!(interner.resolve_cow(invalid), None);
+        }
+
+        #[test]
+        fn get_or_intern_ascii_ci_works() {
+            let mut interner = StringInterner::new();
+            let foo = interner.get_or_intern("FOO");
+            let bar = interner.get_or_intern("bar");
+            assert_eq!(interner.get_or_intern_ascii_ci("foo"), foo);
+            assert_eq!(interner.get_or_intern_ascii_ci("Foo"), foo);
+            assert_eq!(interner.get_or_intern_ascii_ci("BAR"), bar);
+            assert_eq!(interner.resolve(foo), Some("FOO"));
+            let baz = interner.get_or_intern_ascii_ci("Baz");
+            assert_eq!(interner.get_or_intern_ascii_ci("bAZ"), baz);
+            assert_eq!(interner.resolve(baz), Some("Baz"));
+            // Exact lookups stay case-sensitive.
+            let lower_foo = interner.get_or_intern("foo");
+            assert_ne!(lower_foo, foo);
+            assert_eq!(interner.get_or_intern_ascii_ci("fOO"), foo);
+            // Strings interned after the index was created are indexed as well.
+            let qux = interner.get_or_intern("Qux");
+            assert_eq!(interner.get_or_intern_ascii_ci("QUX"), qux);
+            // Only ASCII letters are folded.
+            let sharp = interner.get_or_intern_ascii_ci("ß");
+            assert_ne!(interner.get_or_intern_ascii_ci("SS"), sharp);
+            assert_eq!(interner.len(), 7);
+        }
+
+        #[test]
+        #[cfg(feature = "unicode")]
+        fn get_or_intern_nfc_works() {
+            let mut interner = StringInterner::new();
+            let composed = interner.get_or_intern("caf\u{e9}");
+            let other = interner.get_or_intern("cafe");
+            assert_eq!(interner.get_or_intern_nfc("cafe\u{301}"), composed);
+            assert_eq!(interner.get_or_intern_nfc("cafe"), other);
+            // Strings interned after the index was created are indexed as well
+            // and stored as given.
+            let decomposed = interner.get_or_intern("A\u{30a}");
+            assert_eq!(interner.get_or_intern_nfc("\u{c5}"), decomposed);
+            assert_eq!(interner.resolve(decomposed), Some("A\u{30a}"));
+            // Exact lookups are not normalized.
+            assert_ne!(interner.get_or_intern("\u{c5}"), decomposed);
+            assert_eq!(interner.get_or_intern_nfc("\u{212b}"), decomposed);
+            assert_eq!(interner.len(), 4);
+        }
+
+        #[test]
+        fn get_or_intern_normalized_works() {
+            use std::borrow::Cow;
+
+            fn trim(s: &str) -> Cow<'_, str> {
+                Cow::Borrowed(s.trim())
+            }
+            fn lower(s: &str) -> Cow<'_, str> {
+                Cow::Owned(s.to_lowercase())
+            }
+
+            let mut interner = StringInterner::new();
+            let aa = interner.get_or_intern_normalized(" aa ", trim);
+            assert_eq!(interner.get_or_intern_normalized("aa\t", trim), aa);
+            assert_eq!(interner.get_or_intern("aa"), aa);
+            assert_eq!(interner.resolve(aa), Some("aa"));
+            let bb = interner.get_or_intern_normalized("BB", lower);
+            assert_eq!(interner.get_or_intern_normalized("bB", lower), bb);
+            assert_eq!(interner.resolve(bb), Some("bb"));
+            assert_eq!(interner.len(), 2);
+        }
+
+        #[test]
+        fn canonical_key_works() {
+            use std::borrow::Cow;
+
+            let mut interner = StringInterner::new();
+            let foo = interner.get_or_intern("FOO");
+            let bar = interner.get_or_intern("bar-1");
+            assert_eq!(interner.canonical_key(foo), Some(Cow::Owned("foo".into())));
+            assert!(matches!(interner.canonical_key(bar), Some(Cow::Borrowed("bar-1"))));
+            let invalid = <$symbol>::try_from_usize(1000).unwrap();
+            assert_eq!(interner.canonical_key(invalid), None);
+        }
+
+        #[test]
+        fn new_nodedup_works() {
+            let mut interner = StringInterner::new_nodedup();
+            assert!(!interner.deduplicates());
+            assert!(StringInterner::new().deduplicates());
+            interner.reserve(100);
+            let aa1 = interner.get_or_intern("aa");
+            let bb = interner.get_or_intern("bb");
+            let aa2 = interner.get_or_intern("aa");
+            let static1 = interner.get_or_intern_static("static");
+            let static2 = interner.get_or_intern_static("static");
+            assert_ne!(aa1, aa2);
+            assert_ne!(static1, static2);
+            assert_eq!(interner.len(), 5);
+            assert_eq!(interner.capacity_report().map_capacity, 0);
+            assert_eq!(interner.resolve(aa1), Some("aa"));
+            assert_eq!(interner.resolve(aa2), Some("aa"));
+            assert_eq!(interner.resolve(static2), Some("static"));
+            assert_eq!(interner.get("aa"), Some(aa1));
+            assert_eq!(interner.get("bb"), Some(bb));
+            assert_eq!(interner.get("cc"), None);
+            assert_eq!(interner.iter().count(), 5);
+            let cloned = interner.clone();
+            assert!(!cloned.deduplicates());
+            assert_eq!(cloned.len(), 5);
+        }
+
+        #[test]
+        fn from_sorted_unique_works() {
+            let words = (0..100).map(|i| format!("{:03}", i)).collect::<Vec<_>>();
+            let mut interner = StringInterner::from_sorted_unique(&words);
+            assert_eq!(interner.len(), 100);
+            for (word, (symbol, string)) in words.iter().zip(interner.iter()) {
+                assert_eq!(string, word);
+                assert_eq!(interner.get(word), Some(symbol));
+            }
+            let symbol = interner.get("042").unwrap();
+            assert_eq!(interner.get_or_intern("042"), symbol);
+            assert_eq!(interner.len(), 100);
+            // Duplicates are interned once per occurrence.
+            let duplicated = StringInterner::from_sorted_unique(["a", "a"]);
+            assert_eq!(duplicated.len(), 2);
+            assert_eq!(duplicated.iter().count(), 2);
+            assert!(duplicated.get("a").is_some());
+        }
+
+        #[test]
+        fn try_resolve_works() {
+            let mut interner = StringInterner::new();
+            let aa = interner.get_or_intern("aa");
+            assert_eq!(interner.try_resolve(aa), Ok("aa"));
+            let invalid = <$symbol>::try_from_usize(1000).unwrap();
+            let error = interner.try_resolve(invalid).unwrap_err();
+            assert_eq!(error.index(), 1000);
+            assert_eq!(error.interner_len(), 1);
+            assert_eq!(
+                InternerError::from(error).to_string(),
+                "symbol 1000 does not refer to any of the 1 interned strings"
+            );
+        }
+
+        #[test]
+        #[cfg(feature = "error-in-core")]
+        fn interner_error_source_works() {
+            use core::error::Error;
+
+            let interner = StringInterner::new();
+            let invalid = <$symbol>::try_from_usize(1000).unwrap();
+            let error = InternerError::from(interner.try_resolve(invalid).unwrap_err());
+            let source = error.source().unwrap();
+            assert_eq!(source.to_string(), error.to_string());
+        }
+
+        #[test]
+        fn resolve_shared_works() {
+            use std::sync::Arc;
+            use string_interner::SymbolMap;
+
+            let mut interner = StringInterner::new();
+            let aa = interner.get_or_intern("aa");
+            let bb = interner.get_or_intern("bb");
+            let shared = interner.resolve_shared(aa).unwrap();
+            assert_eq!(&*shared, "aa");
+            assert!(!Arc::ptr_eq(&shared, &interner.resolve_shared(aa).unwrap()));
+            let handle = std::thread::spawn(move || shared.len());
+            assert_eq!(handle.join().unwrap(), 2);
+            let mut arc_cache = SymbolMap::new();
+            let a1 = interner.resolve_shared_cached(aa, &mut arc_cache).unwrap();
+            let a2 = interner.resolve_shared_cached(aa, &mut arc_cache).unwrap();
+            let b1 = interner.resolve_shared_cached(bb, &mut arc_cache).unwrap();
+            assert!(Arc::ptr_eq(&a1, &a2));
+            assert_eq!((&*a1, &*b1), ("aa", "bb"));
+            assert_eq!(arc_cache.len(), 2);
+            let invalid = <$symbol>::try_from_usize(1000).unwrap();
+            assert_eq!(interner.resolve_shared(invalid), None);
+            assert_eq!(interner.resolve_shared_cached(invalid, &mut arc_cache), None);
+            assert_eq!(arc_cache.len(), 2);
+        }
+
+        #[test]
+        #[cfg(feature = "generations")]
+        fn generations_work() {
+            use string_interner::backend::Backend;
+
+            let mut interner = StringInterner::new();
+            assert_eq!(interner.current_generation(), 0);
+            let aa = interner.get_or_intern("aa");
+            let bb = interner.get_or_intern_static("bb");
+            assert_eq!(interner.get_or_intern("aa"), aa);
+            let cc = interner.get_or_intern_ascii_ci("CC");
+            assert_eq!(interner.current_generation(), 3);
+            assert_eq!(interner.generation_of(aa), Some(0));
+            assert_eq!(interner.generation_of(bb), Some(1));
+            assert_eq!(interner.generation_of(cc), Some(2));
+            let invalid = <$symbol>::try_from_usize(1000).unwrap();
+            assert_eq!(interner.generation_of(invalid), None);
+            assert_eq!(interner.clone().generation_of(cc), Some(2));
+            let mut backend = <backend::$backend<$symbol>>::default();
+            let xx = backend.intern("xx");
+            let interner = StringInterner::from_backend(backend);
+            assert_eq!(interner.generation_of(xx), Some(0));
+            assert_eq!(interner.current_generation(), 1);
+        }
+
+        #[test]
+        #[cfg(feature = "stats")]
+        fn stats_work() {
+            use string_interner::InternStats;
+
+            let mut interner = StringInterner::new();
+            assert_eq!(interner.stats(), InternStats::default());
+            interner.get_or_intern("aa");
+            interner.get_or_intern_static("bb");
+            interner.get_or_intern("aa");
+            interner.get_or_intern("bb");
+            interner.get_or_intern("a");
+            interner.get_or_intern("a");
+            assert_eq!(interner.stats(), InternStats { hits: 3, misses: 3 });
+            assert_eq!(interner.clone().stats(), interner.stats());
+            interner.reset_stats();
+            assert_eq!(interner.stats(), InternStats::default());
+            let mut nodedup = StringInterner::new_nodedup();
+            nodedup.get_or_intern("aa");
+            nodedup.get_or_intern("aa");
+            assert_eq!(nodedup.stats(), InternStats { hits: 0, misses: 2 });
+        }
+
+        #[test]
+        #[cfg(feature = "stats")]
+        fn stats_count_every_hit() {
+            use string_interner::InternStats;
+
+            let mut interner = StringInterner::new();
+            for _ in 0..3 {
+                interner.get_or_intern_static("kw");
+            }
+            assert_eq!(interner.stats(), InternStats { hits: 2, misses: 1 });
+            interner.get_or_intern_leaked(String::from("kw"));
+            interner.get_or_intern_ascii_ci("KW");
+            assert_eq!(interner.stats(), InternStats { hits: 4, misses: 1 });
+        }
+
+        #[test]
+        fn get_or_intern_bytes_works() {
+            let mut interner = StringInterner::new();
+            let foo = interner.get_or_intern("foo");
+            assert_eq!(interner.get_or_intern_bytes(b"foo"), Ok(foo));
+            let bar = interner.get_or_intern_bytes("bär".as_bytes()).unwrap();
+            assert_eq!(interner.get_or_intern("bär"), bar);
+            assert_eq!(interner.get("bär"), Some(bar));
+            assert!(interner.get_or_intern_bytes(&[b'f', 0xff]).is_err());
+            assert_eq!(interner.len(), 2);
+        }
+
+        #[test]
+        fn intern_whitespace_split_works() {
+            let mut interner = StringInterner::new();
+            assert!(interner.intern_whitespace_split(" \t\n ").is_empty());
+            let symbols = interner.intern_whitespace_split("a  b\u{3000}a\r\nc ");
+            let strings = symbols
+                .iter()
+                .map(|&symbol| interner.resolve(symbol).unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(strings, ["a", "b", "a", "c"]);
+            assert_eq!(symbols[0], symbols[2]);
+            assert_eq!(interner.len(), 3);
+        }
+
+        #[test]
+        #[cfg(feature = "std")]
+        fn intern_lines_works() {
+            let mut interner = StringInterner::new();
+            let bb = interner.get_or_intern("bb");
+            let symbols = interner.intern_lines("aa\nbb\r\n\naa\r".as_bytes()).unwrap();
+            assert_eq!(symbols.len(), 4);
+            assert_eq!(symbols[1], bb);
+            assert_eq!(interner.resolve(symbols[2]), Some(""));
+            assert_eq!(interner.resolve(symbols[3]), Some("aa\r"));
+            assert!(interner.intern_lines("".as_bytes()).unwrap().is_empty());
+            let error = interner.intern_lines(&b"cc\n\xFF\n"[..]).unwrap_err();
+            assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+            assert!(interner.get("cc").is_some());
+        }
+
+        #[test]
+        fn resolve_owned_if_volatile_works() {
+            use std::borrow::Cow;
+            use string_interner::backend::{AccessKind, Backend};
+            let mut interner = StringInterner::new();
+            let aa = interner.get_or_intern("aa");
+            let resolved = interner.resolve_owned_if_volatile(aa).unwrap();
+            assert_eq!(resolved, "aa");
+            match <backend::$backend<$symbol> as Backend>::ACCESS_KIND {
+                AccessKind::Stable => assert!(matches!(resolved, Cow::Borrowed(_))),
+                AccessKind::Volatile => assert!(matches!(resolved, Cow::Owned(_))),
+            }
+            assert_eq!(interner.resolve_owned_if_volatile(<$symbol>::try_from_usize(1000).unwrap()), None);
+        }
+
+        #[test]
+        fn get_or_intern_leaked_works() {
+            let mut interner = StringInterner::new();
+            let aa = interner.get_or_intern("aa");
+            assert_eq!(interner.get_or_intern_leaked(String::from("aa")), aa);
+            let bb = interner.get_or_intern_leaked(String::from("bb"));
+            assert_ne!(aa, bb);
+            assert_eq!(interner.get_or_intern("bb"), bb);
+            assert_eq!(interner.get_or_intern_leaked(String::from("bb")), bb);
+            assert_eq!(interner.resolve(bb), Some("bb"));
+            assert_eq!(interner.len(), 2);
+        }
+
+        #[test]
+        fn get_or_intern_id_works() {
+            let mut interner = StringInterner::new();
+            let aa = interner.get_or_intern_id("aa");
+            let bb = interner.get_or_intern_id("bb");
+            assert_eq!(interner.get_or_intern_id("aa"), aa);
+            assert_eq!(interner.get("bb").map(Symbol::to_usize), Some(bb));
+            assert_eq!(interner.resolve_id(aa), Some("aa"));
+            assert_eq!(interner.resolve_id(bb), Some("bb"));
+            assert_eq!(interner.resolve_id(1000), None);
+            assert_eq!(interner.resolve_id(1 << 16), None);
+            assert_eq!(interner.resolve_id(1 << 32), None);
+            assert_eq!(interner.resolve_id(usize::MAX), None);
+        }
+
+        #[test]
+        fn resolve_all_works() {
+            let mut interner = StringInterner::new();
+            assert!(interner.resolve_all().is_empty());
+            interner.get_or_intern("aa");
+            interner.get_or_intern_static("bb");
+            interner.get_or_intern("");
+            assert_eq!(interner.resolve_all(), ["aa", "bb", ""]);
+        }
+
+        #[test]
+        #[cfg(feature = "store-hashes")]
+        fn hash_of_works() {
+            use core::hash::BuildHasher;
+
+            let mut interner = StringInterner::new();
+            let aa = interner.get_or_intern("aa");
+            let bb = interner.get_or_intern_static("bb");
+            let cc = interner.try_get_or_intern("cc").unwrap();
+            for (symbol, string) in [(aa, "aa"), (bb, "bb"), (cc, "cc")] {
+                let hash = interner.hasher().hash_one(string);
+                assert_eq!(interner.hash_of(symbol), Some(hash));
+                assert_eq!(interner.resolve_with_hash(symbol), Some((string, hash)));
+            }
+            let invalid = <$symbol>::try_from_usize(1000).unwrap();
+            assert_eq!(interner.hash_of(invalid), None);
+            let mut nodedup = StringInterner::new_nodedup();
+            let dd = nodedup.get_or_intern("dd");
+            assert_eq!(nodedup.hash_of(dd), Some(nodedup.hasher().hash_one("dd")));
+            let mut backend = <backend::$backend<$symbol>>::default();
+            let ee = string_interner::backend::Backend::intern(&mut backend, "ee");
+            let interner = StringInterner::from_backend(backend);
+            assert_eq!(interner.hash_of(ee), Some(interner.hasher().hash_one("ee")));
+        }
+
+        #[test]
+        fn required_symbol_bits_works() {
+            use string_interner::symbol::{SymbolU16, SymbolU32};
+
+            let mut interner = StringInterner::new();
+            assert_eq!(interner.required_symbol_bits(), 0);
+            assert!(interner.fits_in::<SymbolU16>());
+            interner.get_or_intern("a");
+            let last = interner.get_or_intern("b");
+            let expected = usize::BITS - last.to_usize().leading_zeros();
+            assert_eq!(interner.required_symbol_bits(), expected);
+            assert!(interner.fits_in::<SymbolU16>());
+            for i in 0..70_000 {
+                interner.get_or_intern(i.to_string());
+            }
+            assert!(interner.required_symbol_bits() > 16);
+            assert!(!interner.fits_in::<SymbolU16>());
+            assert!(interner.fits_in::<SymbolU32>());
+        }
+
+        #[test]
+        fn intern_all_works() {
+            let mut interner = StringInterner::new();
+            let bb = interner.get_or_intern("bb");
+            let symbols = interner.intern_all(["aa", "bb", "cc", "aa"]);
+            assert_eq!(symbols.len(), 4);
+            assert_eq!(symbols[1], bb);
+            assert_eq!(symbols[0], symbols[3]);
+            assert_eq!(interner.len(), 3);
+            for (symbol, string) in symbols.iter().zip(["aa", "bb", "cc", "aa"]) {
+                assert_eq!(interner.resolve(*symbol), Some(string));
+            }
+            assert!(interner.intern_all(Vec::<String>::new()).is_empty());
+        }
+
+        #[test]
+        fn intern_all_into_works() {
+            let mut interner = StringInterner::new();
+            let mut symbols = vec![interner.get_or_intern("bb")];
+            interner.intern_all_into(["aa", "bb"], &mut symbols);
+            interner.intern_all_into(Vec::<String>::new(), &mut symbols);
+            interner.intern_all_into(["aa"], &mut symbols);
+            assert_eq!(symbols.len(), 4);
+            assert_eq!(symbols[0], symbols[2]);
+            assert_eq!(symbols[1], symbols[3]);
+            assert_eq!(interner.resolve(symbols[1]), Some("aa"));
+            assert_eq!(interner.len(), 2);
+        }
+
+        #[test]
+        fn intern_dedup_batch_works() {
+            let mut interner = StringInterner::new();
+            let cc = interner.get_or_intern("cc");
+            let input = ["aa", "aa", "bb", "aa", "bb", "cc", "aa", "cc", "cc"];
+            let symbols = interner.intern_dedup_batch(input);
+            assert_eq!(symbols, interner.intern_all(input));
+            assert_eq!(symbols[5], cc);
+            assert_eq!(interner.len(), 3);
+            assert!(interner.intern_dedup_batch([]).is_empty());
+            let mut nodedup = StringInterner::new_nodedup();
+            let symbols = nodedup.intern_dedup_batch(["aa", "aa"]);
+            assert_ne!(symbols[0], symbols[1]);
+            assert_eq!(nodedup.len(), 2);
+        }
+
+        #[test]
+        fn resolve_array_works() {
+            let mut interner = StringInterner::new();
+            let aa = interner.get_or_intern("aa");
+            let bb = interner.get_or_intern("bb");
+            let cc = interner.get_or_intern("cc");
+            let [a, b, c] = interner.resolve_array([aa, bb, cc]);
+            assert_eq!((a, b, c), (Some("aa"), Some("bb"), Some("cc")));
+            assert_eq!(interner.resolve_array([cc, aa]), [Some("cc"), Some("aa")]);
+            assert_eq!(interner.resolve_array([]), []);
+            let invalid = <$symbol>::try_from_usize(1000).unwrap();
+            assert_eq!(interner.resolve_array([aa, invalid]), [Some("aa"), None]);
+        }
+
+        #[test]
+        fn from_backend_works() {
+            use string_interner::backend::Backend;
+
+            let mut backend = <backend::$backend<$symbol>>::with_capacity(10);
+            let aa = backend.intern("aa");
+            let bb = backend.intern("bb");
+            let aa2 = backend.intern("aa");
+            let mut interner = StringInterner::from_backend(backend);
+            assert_eq!(interner.len(), 2);
+            assert_eq!(interner.get("aa"), Some(aa));
+            assert_eq!(interner.get("bb"), Some(bb));
+            assert_eq!(interner.resolve(aa2), Some("aa"));
+            assert_eq!(interner.get_or_intern("aa"), aa);
+            let cc = interner.get_or_intern("cc");
+            assert_eq!(interner.resolve(cc), Some("cc"));
+            assert_eq!(interner.len(), 3);
+        }
+
+        #[test]
+        fn reserve_works() {
+            let mut interner = StringInterner::new();
+            let aa = interner.get_or_intern("aa");
+            interner.reserve(100);
+            assert_eq!(interner.get("aa"), Some(aa));
+            let words = (0..100).map(|i| format!("word-{}", i)).collect::<Vec<_>>();
+            let symbols = words
+                .iter()
+                .map(|word| interner.get_or_intern(word))
+                .collect::<Vec<_>>();
+            for (word, symbol) in words.iter().zip(symbols) {
+                assert_eq!(interner.resolve(symbol), Some(word.as_str()));
+            }
+            assert_eq!(interner.resolve(aa), Some("aa"));
+            assert_eq!(interner.len(), 101);
+        }
+
+        #[test]
+        fn reserve_exact_works() {
+            let mut interner = StringInterner::new();
+            let aa = interner.get_or_intern("aa");
+            let words = (0..100).map(|i| format!("word-{}", i)).collect::<Vec<_>>();
+            let bytes = words.iter().map(String::len).sum();
+            interner.reserve_exact(words.len(), bytes);
+            let bytes_allocated = interner.capacity_report().bytes_allocated;
+            let symbols = words
+                .iter()
+                .map(|word| interner.get_or_intern(word))
+                .collect::<Vec<_>>();
+            for (word, symbol) in words.iter().zip(symbols) {
+                assert_eq!(interner.resolve(symbol), Some(word.as_str()));
+            }
+            assert_eq!(interner.resolve(aa), Some("aa"));
+            assert_eq!(interner.capacity_report().bytes_allocated, bytes_allocated);
+        }
+
+        #[test]
+        fn get_or_intern_hashes_once() {
+            use std::{
+                hash::BuildHasher,
+                sync::{
+                    atomic::{AtomicUsize, Ordering},
+                    Arc,
+                },
+            };
+
+            /// Counts the hashers created by the interner.
+            #[derive(Default, Clone)]
+            struct CountingHashBuilder {
+                count: Arc<AtomicUsize>,
+                inner: DefaultHashBuilder,
+            }
+
+            impl BuildHasher for CountingHashBuilder {
+                type Hasher = <DefaultHashBuilder as BuildHasher>::Hasher;
+
+                fn build_hasher(&self) -> Self::Hasher {
+                    self.count.fetch_add(1, Ordering::Relaxed);
+                    self.inner.build_hasher()
+                }
+            }
+
+            let hash_builder = CountingHashBuilder::default();
+            let count = Arc::clone(&hash_builder.count);
+            let mut interner = string_interner::StringInterner::<
+                backend::$backend<$symbol>,
+                CountingHashBuilder,
+            >::with_hasher(hash_builder);
+            interner.reserve(100);
+            for i in 0..100 {
+                let string = format!("string-{}", i);
+                let before = count.load(Ordering::Relaxed);
+                let symbol = interner.get_or_intern(&string);
+                assert_eq!(count.load(Ordering::Relaxed) - before, 1);
+                let before = count.load(Ordering::Relaxed);
+                assert_eq!(interner.get_or_intern(&string), symbol);
+                assert_eq!(count.load(Ordering::Relaxed) - before, 1);
+            }
+        }
+
+        #[test]
+        fn reserve_prevents_hashmap_resizes() {
+            let mut interner = StringInterner::new();
+            interner.reserve(100_000);
+            let map_capacity = interner.capacity_report().map_capacity;
+            assert!(map_capacity >= 100_000);
+            for i in 0..100_000 {
+                interner.get_or_intern(format!("{}", i));
+            }
+            assert_eq!(interner.len(), 100_000);
+            assert_eq!(interner.capacity_report().map_capacity, map_capacity);
+        }
+
+        #[test]
+        fn capacity_report_works() {
+            let mut interner = StringInterner::new();
+            assert_eq!(interner.capacity_report().strings, 0);
+            interner.get_or_intern("aa");
+            interner.get_or_intern("bb");
+            interner.get_or_intern("aa");
+            let report = interner.capacity_report();
+            assert_eq!(report.strings, 2);
+            assert!(report.bytes_used >= 4);
+            assert!(report.bytes_allocated >= report.bytes_used);
+            assert!(report.map_capacity >= 2);
+            #[cfg(feature = "serde")]
+            {
+                let json = serde_json::to_value(report).unwrap();
+                assert_eq!(json["strings"], 2);
+                assert_eq!(json["bytes_used"], report.bytes_used);
+            }
+        }
+
+        #[test]
+        #[cfg(feature = "serde")]
+        fn serde_roundtrip_works() {
+            let interner = ["aa", "bb", "", "cc"]
+                .into_iter()
+                .collect::<StringInterner>();
+            let json = serde_json::to_string(&interner).unwrap();
+            assert_eq!(json, r#"["aa","bb","","cc"]"#);
+            let deserialized = serde_json::from_str::<StringInterner>(&json).unwrap();
+            assert_eq!(deserialized, interner);
+        }
+
+        #[test]
+        fn shrink_hashmap_to_fit_works() {
+            let mut interner = StringInterner::with_capacity(100);
+            let aa = interner.get_or_intern("aa");
+            let bb = interner.get_or_intern("bb");
+            interner.shrink_hashmap_to_fit();
+            assert_eq!(interner.get("aa"), Some(aa));
+            assert_eq!(interner.get("bb"), Some(bb));
+            let cc = interner.get_or_intern("cc");
+            assert_eq!(interner.get("cc"), Some(cc));
+            assert_eq!(interner.len(), 3);
+        }
+
+        #[test]
+        fn get_or_intern_static_pointer_identity_works() {
+            let mut interner = StringInterner::new();
+            let aa = interner.get_or_intern("aa");
+            let literal: &'static str = "aa";
+            assert_eq!(interner.get_or_intern_static(literal), aa);
+            assert_eq!(interner.get_or_intern_static(literal), aa);
+            // Same contents at a different address take the content path.
+            let leaked: &'static str = Box::leak(String::from("aa").into_boxed_str());
+            assert_eq!(interner.get_or_intern_static(leaked), aa);
+            let bb = interner.get_or_intern_static("bb");
+            assert_eq!(interner.get_or_intern_static("bb"), bb);
+            assert_eq!(interner.get_or_intern("bb"), bb);
+            assert_eq!(interner.len(), 2);
+        }
+
+        #[test]
+        fn try_get_or_intern_respects_max_string_len() {
+            let mut interner = StringInterner::new();
+            assert_eq!(interner.max_string_len(), None);
+            let long = interner.get_or_intern("long string");
+            interner.set_max_string_len(Some(4));
+            assert_eq!(interner.max_string_len(), Some(4));
+            let error = interner.try_get_or_intern("too long").unwrap_err();
+            match error {
+                InternerError::CapacityExceeded(error) => {
+                    assert_eq!(error.string_len(), 8);
+                    assert_eq!(error.capacity(), 4);
+                }
+                error => panic!("unexpected error: {}", error),
+            }
+            assert!(interner.try_get_or_intern("long string").is_err());
+            assert_eq!(interner.len(), 1);
+            let short = interner.try_get_or_intern("four").unwrap();
+            assert_eq!(interner.resolve(short), Some("four"));
+            assert_eq!(interner.get_or_intern("four"), short);
+            interner.set_max_string_len(None);
+            assert_eq!(interner.try_get_or_intern("long string"), Ok(long));
+        }
+
+        #[test]
+        fn try_with_capacity_works() {
+            let mut interner = StringInterner::try_with_capacity(10).unwrap();
+            let aa = interner.get_or_intern("aa");
+            assert_eq!(interner.resolve(aa), Some("aa"));
+            assert!(StringInterner::try_with_capacity(0).is_ok());
+            if let Some(cap) = <$symbol>::MAX_INDEX.checked_add(2) {
+                match StringInterner::try_with_capacity(cap) {
+                    Err(InternerError::SymbolOutOfBounds(error)) => {
+                        assert_eq!(error.index(), cap - 1);
+                        assert_eq!(error.max_index(), <$symbol>::MAX_INDEX);
+                    }
+                    _ => panic!("expected an out of bounds error"),
+                }
+            }
+        }
+
+        #[test]
+        #[should_panic]
+        fn get_or_intern_panics_above_max_string_len() {
+            let mut interner = StringInterner::new();
+            interner.set_max_string_len(Some(1));
+            interner.get_or_intern("aa");
+        }
+
+        #[test]
+        #[should_panic(expected = "string exceeds the maximum string length")]
+        fn get_or_intern_ascii_ci_panics_above_max_string_len() {
+            let mut interner = StringInterner::new();
+            interner.get_or_intern("aaa");
+            interner.set_max_string_len(Some(2));
+            interner.get_or_intern_ascii_ci("AAA");
+        }
+
+        #[test]
+        #[cfg(feature = "unicode")]
+        #[should_panic(expected = "string exceeds the maximum string length")]
+        fn get_or_intern_nfc_panics_above_max_string_len() {
+            let mut interner = StringInterner::new();
+            interner.get_or_intern("caf\u{e9}");
+            interner.set_max_string_len(Some(5));
+            interner.get_or_intern_nfc("cafe\u{301}");
+        }
+
         #[test]
         fn shrink_to_fit_works() {
             let mut interner = StringInterner::new();
@@ -418,12 +1251,435 @@ mod bucket_backend {
     use super::*;
 
     gen_tests_for_backend!(BucketBackend);
+
+    #[test]
+    fn compact_storage_keeps_symbols() {
+        let mut interner = StringInterner::new();
+        let symbols = (0..1000)
+            .map(|i| interner.get_or_intern(format!("string-{}", i)))
+            .collect::<Vec<_>>();
+        interner.compact_storage();
+        assert_eq!(interner.capacity_report().buckets, 1);
+        for (i, &symbol) in symbols.iter().enumerate() {
+            assert_eq!(interner.resolve(symbol), Some(format!("string-{}", i).as_str()));
+            assert_eq!(interner.get(format!("string-{}", i)), Some(symbol));
+        }
+    }
+
+    #[test]
+    fn resolve_owned_if_volatile_borrows() {
+        use std::borrow::Cow;
+        let mut interner = StringInterner::new();
+        let aa = interner.get_or_intern("aa");
+        let resolved = interner.resolve_owned_if_volatile(aa);
+        assert!(matches!(resolved, Some(Cow::Borrowed("aa"))));
+    }
+
+    #[test]
+    fn iter_rev_works() {
+        let mut interner = StringInterner::new();
+        for i in 0..100 {
+            interner.get_or_intern(format!("string-{}", i));
+        }
+        interner.get_or_intern("string-5");
+        let forward = interner.iter().collect::<Vec<_>>();
+        let mut reversed = interner.iter().rev().collect::<Vec<_>>();
+        assert!(reversed
+            .windows(2)
+            .all(|pair| pair[0].0.to_usize() > pair[1].0.to_usize()));
+        reversed.reverse();
+        assert_eq!(reversed, forward);
+    }
+
+    #[test]
+    fn rollback_works() {
+        let mut interner = StringInterner::new();
+        let aa = interner.get_or_intern("aa");
+        let checkpoint = interner.checkpoint();
+        interner.get_or_intern("bb");
+        interner.get_or_intern_static("static");
+        for i in 0..100 {
+            interner.get_or_intern(format!("string-{}", i));
+        }
+        assert_eq!(interner.len(), 103);
+        // SAFETY: The checkpoint was created by this interner.
+        unsafe { interner.rollback(checkpoint) };
+        assert_eq!(interner.len(), 1);
+        assert_eq!(interner.get("aa"), Some(aa));
+        assert_eq!(interner.get("bb"), None);
+        assert_eq!(interner.get("string-50"), None);
+        assert_eq!(interner.get("static"), None);
+        let bb = interner.get_or_intern("bb");
+        assert_eq!(interner.resolve(bb), Some("bb"));
+        assert_eq!(interner.resolve(aa), Some("aa"));
+        let static_ = interner.get_or_intern_static("static");
+        assert_eq!(interner.resolve(static_), Some("static"));
+        assert_eq!(interner.len(), 3);
+    }
+
+    #[test]
+    fn rollback_resets_ascii_ci_index() {
+        let mut interner = StringInterner::new();
+        let aa = interner.get_or_intern_ascii_ci("Aa");
+        let checkpoint = interner.checkpoint();
+        interner.get_or_intern_ascii_ci("BB");
+        interner.get_or_intern_fmt(format_args!("{}{}", "C", "c"));
+        // SAFETY: The checkpoint was created by this interner.
+        unsafe { interner.rollback(checkpoint) };
+        assert_eq!(interner.get_or_intern_ascii_ci("aA"), aa);
+        let bb = interner.get_or_intern_ascii_ci("bb");
+        assert_eq!(interner.resolve(bb), Some("bb"));
+        let cc = interner.get_or_intern_fmt(format_args!("{}{}", "c", "C"));
+        assert_eq!(interner.get_or_intern_ascii_ci("CC"), cc);
+        assert_eq!(interner.len(), 3);
+    }
+
+    #[test]
+    fn swap_remove_works() {
+        let mut interner = StringInterner::new();
+        let aa = interner.get_or_intern("aa");
+        let bb = interner.get_or_intern_static("bb");
+        let cc = interner.get_or_intern("cc");
+        let removed = interner.swap_remove(aa).unwrap();
+        assert_eq!(removed.string, "aa");
+        assert_eq!(removed.moved, Some((cc, aa)));
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.get("aa"), None);
+        assert_eq!(interner.get("cc"), Some(aa));
+        assert_eq!(interner.resolve(cc), None);
+        assert_eq!(interner.swap_remove(cc), None);
+        let removed = interner.swap_remove(bb).unwrap();
+        assert_eq!(removed.moved, None);
+        assert_eq!(interner.get_or_intern_static("bb"), bb);
+        assert_eq!(interner.get_or_intern("cc"), aa);
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn iter_after_swap_remove_yields_valid_symbols() {
+        let mut interner = StringInterner::new();
+        let aa = interner.get_or_intern("aa");
+        let bb = interner.get_or_intern("bb");
+        let cc = interner.get_or_intern("cc");
+        interner.swap_remove(aa);
+        let entries = interner.iter().collect::<Vec<_>>();
+        assert_eq!(entries, [(aa, "cc"), (bb, "bb")]);
+        assert!(entries.iter().all(|&(symbol, _)| interner.is_valid(symbol)));
+        assert!(!interner.is_valid(cc));
+    }
+
+    #[test]
+    fn iter_insertion_order_after_swap_remove() {
+        let mut interner = StringInterner::new();
+        let first = interner.get_or_intern("first");
+        interner.get_or_intern("second");
+        interner.get_or_intern("third");
+        assert!(interner.iter_insertion_order().is_some());
+        interner.swap_remove(first);
+        assert!(interner.iter_insertion_order().is_none());
+        assert!(interner.clone().iter_insertion_order().is_none());
+    }
+
+    #[test]
+    fn nodedup_swap_remove_works() {
+        let mut interner = StringInterner::new_nodedup();
+        let aa = interner.get_or_intern("aa");
+        interner.get_or_intern("aa");
+        assert_eq!(interner.swap_remove(aa).unwrap().string, "aa");
+        assert_eq!(interner.len(), 1);
+        assert_eq!(interner.get("aa"), Some(aa));
+    }
+
+    #[test]
+    #[cfg(feature = "generations")]
+    fn rollback_keeps_generations_unique() {
+        let mut interner = StringInterner::new();
+        let aa = interner.get_or_intern("aa");
+        let checkpoint = interner.checkpoint();
+        let bb = interner.get_or_intern("bb");
+        // SAFETY: The checkpoint was created by this interner.
+        unsafe { interner.rollback(checkpoint) };
+        assert_eq!(interner.generation_of(aa), Some(0));
+        assert_eq!(interner.generation_of(bb), None);
+        let cc = interner.get_or_intern_fmt(format_args!("{}", 'c'));
+        assert_eq!(cc, bb);
+        assert_eq!(interner.generation_of(cc), Some(2));
+    }
+
+    #[test]
+    fn convert_backend_works() {
+        let mut interner = StringInterner::new();
+        for i in 0..100 {
+            interner.get_or_intern(format!("string-{}", i));
+        }
+        interner.get_or_intern_static("static");
+        interner.set_max_string_len(Some(20));
+        let strings = interner
+            .iter()
+            .map(|(symbol, string)| (symbol, string.to_owned()))
+            .collect::<Vec<_>>();
+
+        let converted = interner.clone().convert_backend::<backend::StringBackend>();
+        assert_eq!(converted.len(), 101);
+        assert_eq!(converted.max_string_len(), Some(20));
+        for (symbol, string) in &strings {
+            assert_eq!(converted.resolve(*symbol), Some(string.as_str()));
+            assert_eq!(converted.get(string), Some(*symbol));
+        }
+
+        // The buffer backend assigns different symbols but keeps the order.
+        let converted = interner.convert_backend::<backend::BufferBackend>();
+        assert_eq!(converted.len(), 101);
+        let converted_strings = converted.iter().map(|(_, string)| string).collect::<Vec<_>>();
+        assert_eq!(converted_strings, strings.iter().map(|(_, s)| s.as_str()).collect::<Vec<_>>());
+        for (_, string) in &strings {
+            let symbol = converted.get(string).unwrap();
+            assert_eq!(converted.resolve(symbol), Some(string.as_str()));
+        }
+
+        let mut nodedup = StringInterner::new_nodedup();
+        nodedup.get_or_intern("aa");
+        nodedup.get_or_intern("aa");
+        let mut converted = nodedup.convert_backend::<backend::StringBackend>();
+        assert!(!converted.deduplicates());
+        assert_eq!(converted.len(), 2);
+        converted.get_or_intern("aa");
+        assert_eq!(converted.len(), 3);
+    }
+
+    #[test]
+    fn nodedup_rollback_works() {
+        let mut interner = StringInterner::new_nodedup();
+        let aa = interner.get_or_intern("aa");
+        let checkpoint = interner.checkpoint();
+        interner.get_or_intern("aa");
+        let fmt1 = interner.get_or_intern_fmt(format_args!("{}-{}", "b", 1));
+        let fmt2 = interner.get_or_intern_fmt(format_args!("{}-{}", "b", 1));
+        assert_ne!(fmt1, fmt2);
+        assert_eq!(interner.len(), 4);
+        // SAFETY: The checkpoint was created by this interner.
+        unsafe { interner.rollback(checkpoint) };
+        assert_eq!(interner.len(), 1);
+        assert_eq!(interner.get("aa"), Some(aa));
+        assert_eq!(interner.get("b-1"), None);
+        assert_ne!(interner.get_or_intern("aa"), aa);
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    #[cfg_attr(any(miri, not(feature = "test-allocations")), ignore)]
+    fn tiny_backend_allocations() {
+        use string_interner::backend::Backend;
+
+        ALLOCATOR.reset();
+        ALLOCATOR.start_profiling();
+        let mut backend = backend::BucketBackend::<DefaultSymbol>::default();
+        assert_eq!(ALLOCATOR.stats().len_allocations(), 0);
+        for string in ["aa", "bb", "cc"] {
+            let _ = backend.intern(string);
+        }
+        drop(backend);
+        ALLOCATOR.end_profiling();
+        // Two head buckets, one for the full buckets and one for the spans
+        // unless they are stored inline.
+        let expected = if cfg!(feature = "smallvec-spans") { 3 } else { 4 };
+        assert_eq!(ALLOCATOR.stats().len_allocations(), expected);
+        assert_eq!(ALLOCATOR.stats().len_deallocations(), expected);
+    }
+
+    #[test]
+    fn get_or_intern_fmt_works() {
+        let mut interner = StringInterner::new();
+        let aa = interner.get_or_intern("aa::bb");
+        let checkpoint = interner.checkpoint();
+        let sym = string_interner::get_or_intern_fmt!(interner, "{}::{}", "aa", "bb");
+        assert_eq!(sym, aa);
+        assert_eq!(interner.checkpoint(), checkpoint);
+        let symbols = (0..100)
+            .map(|i| interner.get_or_intern_fmt(format_args!("string-{}", i)))
+            .collect::<Vec<_>>();
+        for (i, symbol) in symbols.into_iter().enumerate() {
+            let string = format!("string-{}", i);
+            assert_eq!(interner.resolve(symbol), Some(string.as_str()));
+            assert_eq!(interner.get(&string), Some(symbol));
+        }
+        let literal = interner.get_or_intern_fmt(format_args!("aa::bb"));
+        assert_eq!(literal, aa);
+        assert_eq!(interner.len(), 101);
+    }
+
+    #[test]
+    fn symbol_exhaustion_panics_at_caller() {
+        use std::{
+            panic,
+            sync::{Arc, Mutex},
+            thread,
+        };
+        use string_interner::symbol::SymbolU16;
+
+        let mut interner = string_interner::StringInterner::<
+            backend::BucketBackend<SymbolU16>,
+            DefaultHashBuilder,
+        >::new();
+        for i in 0..=SymbolU16::MAX_INDEX {
+            interner.get_or_intern(i.to_string());
+        }
+        let test_thread = thread::current().id();
+        let location = Arc::new(Mutex::new(None));
+        let hook_location = Arc::clone(&location);
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if thread::current().id() == test_thread {
+                *hook_location.lock().unwrap() = info.location().map(|l| l.file().to_owned());
+            } else {
+                default_hook(info);
+            }
+        }));
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            interner.get_or_intern("overflow");
+        }));
+        let _ = panic::take_hook();
+        assert!(result.is_err());
+        assert_eq!(location.lock().unwrap().as_deref(), Some(file!()));
+    }
+
+    #[test]
+    fn with_symbol_type_rejects_narrowing() {
+        use string_interner::symbol::SymbolU16;
+
+        let mut interner = StringInterner::new();
+        for i in 0..=SymbolU16::MAX_INDEX {
+            interner.get_or_intern(i.to_string());
+        }
+        let narrowed = interner.clone().with_symbol_type::<SymbolU16>().unwrap();
+        assert_eq!(narrowed.len(), SymbolU16::MAX_INDEX + 1);
+        interner.get_or_intern("overflow");
+        let error = interner.with_symbol_type::<SymbolU16>().unwrap_err();
+        assert_eq!(error.index(), SymbolU16::MAX_INDEX + 1);
+    }
+
+    #[test]
+    fn resolve_all_is_indexed_by_symbol() {
+        let mut interner = StringInterner::new();
+        let aa = interner.get_or_intern("aa");
+        let bb = interner.get_or_intern_static("bb");
+        let empty = interner.get_or_intern("");
+        let strings = interner.resolve_all();
+        for symbol in [aa, bb, empty] {
+            assert_eq!(Some(strings[symbol.to_usize()]), interner.resolve(symbol));
+        }
+    }
+
+    #[test]
+    fn get_or_intern_grouped_works() {
+        let mut interner = StringInterner::new();
+        let a = interner.get_or_intern_grouped(3, "a");
+        assert_eq!(interner.get_or_intern("a"), a);
+        assert_eq!(interner.get_or_intern_grouped(4, "a"), a);
+        let b = interner.get_or_intern_grouped(4, "b");
+        assert_eq!(interner.get("b"), Some(b));
+        assert_eq!(interner.len(), 2);
+        let mut nodedup = StringInterner::new_nodedup();
+        let a = nodedup.get_or_intern_grouped(3, "a");
+        assert_ne!(nodedup.get_or_intern_grouped(3, "a"), a);
+        assert_eq!(nodedup.len(), 2);
+    }
+
+    #[test]
+    #[cfg(all(feature = "validate", debug_assertions))]
+    #[should_panic(expected = "deduplication failed")]
+    fn inconsistent_hashes_are_detected() {
+        use core::hash::BuildHasher;
+
+        /// Seeds every hasher differently so that equal strings hash differently.
+        #[derive(Default)]
+        struct InconsistentHashBuilder;
+
+        impl BuildHasher for InconsistentHashBuilder {
+            type Hasher = <DefaultHashBuilder as BuildHasher>::Hasher;
+
+            fn build_hasher(&self) -> Self::Hasher {
+                DefaultHashBuilder::default().build_hasher()
+            }
+        }
+
+        let mut interner = string_interner::StringInterner::<
+            backend::BucketBackend<DefaultSymbol>,
+            InconsistentHashBuilder,
+        >::with_hasher(InconsistentHashBuilder);
+        // Strings of three bytes bypass the `small-string-cache`.
+        for _ in 0..100 {
+            interner.get_or_intern("aaa");
+        }
+    }
+
+    #[test]
+    fn backend_accessors_work() {
+        let mut interner = StringInterner::new();
+        let aa = interner.get_or_intern("aa");
+        assert_eq!(interner.backend().buckets().collect::<Vec<_>>(), ["aa"]);
+        // SAFETY: Changing the size of future buckets keeps all strings.
+        unsafe { interner.backend_mut() }.set_fixed_bucket_size(Some(4));
+        let bbb = interner.get_or_intern("bbb");
+        assert_eq!(interner.backend().fixed_bucket_size(), Some(4));
+        assert_eq!(interner.backend().strings_in_bucket(1).collect::<Vec<_>>(), ["bbb"]);
+        assert_eq!(interner.resolve(aa), Some("aa"));
+        assert_eq!(interner.get("bbb"), Some(bbb));
+    }
+
+    #[test]
+    fn get_or_intern_pair_works() {
+        let mut interner = StringInterner::new();
+        let (ab, ba) = interner.get_or_intern_pair("ab");
+        assert_eq!(interner.resolve(ab), Some("ab"));
+        assert_eq!(interner.resolve(ba), Some("ba"));
+        assert_eq!(interner.get_or_intern_pair("ba"), (ba, ab));
+        let (aba, reversed) = interner.get_or_intern_pair("aba");
+        assert_eq!(aba, reversed);
+        // Chars are reversed, not bytes.
+        let (_, reversed) = interner.get_or_intern_pair("aé");
+        assert_eq!(interner.resolve(reversed), Some("éa"));
+        let (empty, reversed) = interner.get_or_intern_pair("");
+        assert_eq!((interner.resolve(empty), empty), (Some(""), reversed));
+        assert_eq!(interner.len(), 6);
+    }
+
+    #[test]
+    fn get_or_intern_chars_works() {
+        let mut interner = StringInterner::new();
+        let aa = interner.get_or_intern("aa");
+        let checkpoint = interner.checkpoint();
+        assert_eq!(interner.get_or_intern_chars("aa".chars()), aa);
+        assert_eq!(interner.checkpoint(), checkpoint);
+        let empty = interner.get_or_intern_chars(std::iter::empty());
+        assert_eq!(interner.resolve(empty), Some(""));
+        assert_eq!(interner.get_or_intern(""), empty);
+        let unicode = interner.get_or_intern_chars("ß€😀".chars().rev());
+        assert_eq!(interner.get("😀€ß"), Some(unicode));
+        assert_eq!(interner.len(), 3);
+    }
 }
 
 mod string_backend {
     use super::*;
 
     gen_tests_for_backend!(StringBackend);
+
+    #[test]
+    fn iter_rev_works() {
+        let mut interner = StringInterner::new();
+        for i in 0..100 {
+            interner.get_or_intern(format!("string-{}", i));
+        }
+        interner.get_or_intern("string-5");
+        let forward = interner.iter().collect::<Vec<_>>();
+        let mut reversed = interner.iter().rev().collect::<Vec<_>>();
+        assert!(reversed
+            .windows(2)
+            .all(|pair| pair[0].0.to_usize() > pair[1].0.to_usize()));
+        reversed.reverse();
+        assert_eq!(reversed, forward);
+    }
 }
 
 mod buffer_backend {